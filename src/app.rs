@@ -1,4 +1,7 @@
-use crate::{content::Content, events::EventHandler, popup::Popup, screen::Screen, CliConfig};
+use crate::{
+    content::Content, events::EventHandler, external_editor, packs, popup::Popup, screen::Screen,
+    search::Search, spell::SpellChecker, templates, vim::Vim, CliConfig,
+};
 use color_eyre::Result;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
@@ -16,46 +19,114 @@ pub struct App {
     screen: Rc<RefCell<Screen>>,
     popup: Rc<RefCell<Popup>>,
     content: Rc<RefCell<Content>>,
+    search: Rc<RefCell<Search>>,
+    vim: Rc<RefCell<Vim>>,
+    spell: Rc<RefCell<SpellChecker>>,
+    mouse_capture: bool,
 }
 
 impl App {
-    pub fn new(cli_config: CliConfig) -> App {
-        let screen = Rc::new(RefCell::new(Screen::new()));
+    pub fn new(mut cli_config: CliConfig) -> App {
+        let mut screen = Screen::new();
+        screen.set_key_hints(&cli_config.keymap);
+        screen.output_dir = cli_config.exports.output_dir.clone();
+        screen.markdown_style = cli_config.markdown_style.clone();
+        screen.set_render_options(cli_config.render_options.clone());
+        screen.stats_targets = cli_config.stats_targets.clone();
+        screen.lint_rules = cli_config.lint_rules.clone();
+        screen.theme = cli_config.theme;
+        let mut content = Content::with_definitions(cli_config.render_options.sections.clone());
+        content.theme = cli_config.theme;
+        content.show_cursor = cli_config.vim;
+        let template_dir = &cli_config.render_options.sections.template_dir;
+        if cli_config.saved_document.is_none() && !template_dir.is_dir() {
+            match packs::available() {
+                Ok(packs) => screen.pack_picker.open(packs),
+                Err(error) => screen.status_message = Some(error.to_string()),
+            }
+        }
+        if let Some(saved) = cli_config.saved_document.take() {
+            content.file_to_save = saved.sections;
+            content.front_matter = saved.front_matter;
+            if let Some(cursor) = saved.cursor {
+                screen.select(Content::index_for_section(&cursor.section), &mut content);
+                content.restore_cursor(cursor.line, cursor.column);
+            }
+        }
+        if cli_config.spell_checker.enabled {
+            cli_config.spell_checker.enabled = false;
+            if let Some(message) = cli_config.spell_checker.toggle() {
+                screen.status_message = Some(message);
+            }
+        }
+        let template_problems = templates::check_content(&content);
+        if !template_problems.is_empty() {
+            screen.status_message = Some(format!(
+                "{} template problem(s) found - see the diagnostics panel",
+                template_problems.len()
+            ));
+            screen.show_diagnostics = true;
+        }
+        let screen = Rc::new(RefCell::new(screen));
         let popup = Rc::new(RefCell::new(Popup::new()));
-        let content = Rc::new(RefCell::new(Content::new()));
+        let content = Rc::new(RefCell::new(content));
+        let search = Rc::new(RefCell::new(Search::new()));
+        let vim = Rc::new(RefCell::new(Vim::new(cli_config.vim)));
+        let spell = Rc::new(RefCell::new(std::mem::take(&mut cli_config.spell_checker)));
         let event_handler = EventHandler::new(
             Rc::clone(&screen),
             Rc::clone(&popup),
             Rc::clone(&content),
-            cli_config.output_dir.clone(),
-            cli_config.file_name.clone(),
+            Rc::clone(&search),
+            Rc::clone(&vim),
+            Rc::clone(&spell),
+            &cli_config,
         );
         Self {
             event_handler,
             screen,
             popup,
             content,
+            search,
+            vim,
+            spell,
+            mouse_capture: cli_config.mouse_capture,
         }
     }
 
     pub fn run(&mut self) -> Result<()> {
-        terminal::enable_raw_mode()?;
-        let mut stdout = stdout();
-        stdout.execute(EnterAlternateScreen)?;
-        stdout.execute(EnableMouseCapture)?;
+        self.enter_terminal()?;
 
-        let backend = CrosstermBackend::new(stdout);
+        let backend = CrosstermBackend::new(stdout());
         let mut terminal = Terminal::new(backend)?;
 
         let res = self.run_app(&mut terminal);
 
-        terminal::disable_raw_mode()?;
-        terminal.backend_mut().execute(LeaveAlternateScreen)?;
-        terminal.backend_mut().execute(DisableMouseCapture)?;
+        self.leave_terminal()?;
 
         res
     }
 
+    fn enter_terminal(&self) -> Result<()> {
+        terminal::enable_raw_mode()?;
+        let mut stdout = stdout();
+        stdout.execute(EnterAlternateScreen)?;
+        if self.mouse_capture {
+            stdout.execute(EnableMouseCapture)?;
+        }
+        Ok(())
+    }
+
+    fn leave_terminal(&self) -> Result<()> {
+        terminal::disable_raw_mode()?;
+        let mut stdout = stdout();
+        stdout.execute(LeaveAlternateScreen)?;
+        if self.mouse_capture {
+            stdout.execute(DisableMouseCapture)?;
+        }
+        Ok(())
+    }
+
     fn run_app(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
         loop {
             terminal.draw(|f| {
@@ -63,9 +134,17 @@ impl App {
                     f,
                     &mut self.popup.borrow_mut(),
                     &mut self.content.borrow_mut(),
+                    &mut self.search.borrow_mut(),
+                    &self.vim.borrow(),
+                    &mut self.spell.borrow_mut(),
                 )
             })?;
-            self.event_handler.listen_for_keyboard_events()?;
+            self.event_handler.listen_for_events()?;
+
+            if self.event_handler.external_edit_requested {
+                self.event_handler.external_edit_requested = false;
+                self.open_in_external_editor(terminal)?;
+            }
 
             if self.event_handler.should_quit {
                 break;
@@ -73,4 +152,90 @@ impl App {
         }
         Ok(())
     }
+
+    fn open_in_external_editor(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<()> {
+        let (section, lines) = {
+            let content = self.content.borrow();
+            match content.current_section() {
+                Some(section) => (section, content.content_input.clone()),
+                None => return Ok(()),
+            }
+        };
+        self.leave_terminal()?;
+        let edited = external_editor::edit_lines(&format!("{:?}", section), &lines);
+        self.enter_terminal()?;
+        terminal.clear()?;
+
+        match edited {
+            Ok(Some(edited_lines)) => self
+                .content
+                .borrow_mut()
+                .set_section_lines(&section, edited_lines),
+            Ok(None) => {
+                self.screen.borrow_mut().status_message =
+                    Some("Editor exited with an error, changes were discarded".to_string())
+            }
+            Err(error) => self.screen.borrow_mut().status_message = Some(error.to_string()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        content::ContentListItem,
+        document::RenderOptions,
+        export::Exports,
+        includes::Includes,
+        keymap::Keymap,
+        lint::LintRules,
+        markdown::MarkdownStyle,
+        snippets::SnippetLibrary,
+        source::{Cursor, DocumentSource, SavedDocument},
+        stats::StatsTargets,
+        theme::Theme,
+    };
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::{collections::HashMap, path::PathBuf};
+
+    #[test]
+    fn test_restored_cursor_survives_first_edit() {
+        let lines = vec!["# Guides".to_string(), "Run the build".to_string()];
+        let mut app = App::new(CliConfig {
+            exports: Exports::default(),
+            source: DocumentSource::default(),
+            saved_document: Some(SavedDocument {
+                sections: HashMap::from([(ContentListItem::Guides, lines)]),
+                front_matter: HashMap::new(),
+                cursor: Some(Cursor {
+                    section: ContentListItem::Guides,
+                    line: 1,
+                    column: 4,
+                }),
+            }),
+            mouse_capture: false,
+            vim: false,
+            render_options: RenderOptions::default(),
+            markdown_style: MarkdownStyle::default(),
+            spell_checker: SpellChecker::default(),
+            stats_targets: StatsTargets::default(),
+            lint_rules: LintRules::default(),
+            theme: Theme::default(),
+            keymap: Keymap::default(),
+            snippets: SnippetLibrary::default(),
+            includes: Includes::default(),
+            project_config: PathBuf::from(".txtui.toml"),
+        });
+        app.event_handler
+            .handle_navigation_input(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE))
+            .unwrap();
+        let content = app.content.borrow();
+        assert!(content.enable_insert_mode);
+        assert_eq!((content.cursor_index_y, content.cursor_index_x), (1, 4));
+    }
 }
@@ -0,0 +1,146 @@
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::content::ContentListItem;
+use crate::document::{self, RenderOptions};
+use crate::export::Exports;
+use crate::front_matter::PageMetadata;
+use crate::includes::{self, Includes};
+use crate::markdown::MarkdownStyle;
+use crate::sections::section_key;
+
+pub fn saved_sections(
+    lines: &[String],
+    render_options: &RenderOptions,
+    markdown_style: &MarkdownStyle,
+) -> HashMap<ContentListItem, Vec<String>> {
+    document::parse(&includes::collapse(lines), &render_options.sections)
+        .into_iter()
+        .map(|parsed| {
+            let lines = if markdown_style.format_on_save {
+                markdown_style.format_section(&parsed.lines)
+            } else {
+                parsed.lines
+            };
+            (parsed.section, lines)
+        })
+        .collect()
+}
+
+pub fn rebuild(
+    lines: &[String],
+    render_options: &RenderOptions,
+    markdown_style: &MarkdownStyle,
+) -> Vec<String> {
+    document::render(
+        &saved_sections(lines, render_options, markdown_style),
+        render_options,
+    )
+}
+
+pub fn read_document(
+    path: &Path,
+    render_options: &RenderOptions,
+    markdown_style: &MarkdownStyle,
+) -> Result<HashMap<ContentListItem, Vec<String>>> {
+    if !path.is_file() {
+        return Err(eyre!(
+            "No saved document at {} - run `txtui edit` and save first",
+            path.display()
+        ));
+    }
+    let data =
+        fs::read_to_string(path).wrap_err_with(|| format!("Unable to read {}", path.display()))?;
+    let lines: Vec<String> = data.lines().map(|line| line.to_string()).collect();
+    Ok(saved_sections(&lines, render_options, markdown_style))
+}
+
+pub fn run(
+    sections: &HashMap<ContentListItem, Vec<String>>,
+    front_matter: &HashMap<ContentListItem, PageMetadata>,
+    exports: &Exports,
+    includes: &Includes,
+    render_options: &RenderOptions,
+    check: bool,
+) -> Result<bool> {
+    let (sections, include_errors) = includes.expand_sections(sections);
+    for (section, error) in &include_errors {
+        println!(
+            "{}:{}: {}",
+            section_key(section),
+            error.line + 1,
+            error.message
+        );
+    }
+    let outputs = exports.outputs(&sections, front_matter, render_options);
+    let mut up_to_date = include_errors.is_empty();
+    for (output_path, rendered) in &outputs {
+        let current: Option<Vec<String>> = fs::read_to_string(output_path)
+            .ok()
+            .map(|data| data.lines().map(|line| line.to_string()).collect());
+        if current.as_ref() == Some(rendered) {
+            println!("{} is up to date", output_path.display());
+        } else if check {
+            println!(
+                "{} is out of date - run `txtui build`",
+                output_path.display()
+            );
+            up_to_date = false;
+        } else {
+            Exports::write(std::slice::from_ref(&(
+                output_path.clone(),
+                rendered.clone(),
+            )))
+            .wrap_err_with(|| format!("Unable to write {}", output_path.display()))?;
+            println!("wrote {}", output_path.display());
+        }
+    }
+    Ok(up_to_date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+
+    #[test]
+    fn test_rebuild_applies_render_options() {
+        let saved = document::render(
+            &HashMap::from([
+                (ContentListItem::ProjectName, lines(&["txtui"])),
+                (ContentListItem::Guides, lines(&["# Build", "* step"])),
+            ]),
+            &RenderOptions::default(),
+        );
+        let options = RenderOptions::default();
+        assert_eq!(rebuild(&saved, &options, &MarkdownStyle::default()), saved);
+
+        let options = RenderOptions {
+            toc_depth: Some(3),
+            ..RenderOptions::default()
+        };
+        let mut style = MarkdownStyle::default();
+        style.format_on_save = true;
+        let rebuilt = rebuild(&saved, &options, &style);
+        assert!(rebuilt.contains(&"- [Guides](#guides)".to_string()));
+        assert!(rebuilt.contains(&"- step".to_string()));
+        assert_eq!(rebuild(&rebuilt, &options, &style), rebuilt);
+    }
+
+    #[test]
+    fn test_included_headings_do_not_split_sections() {
+        let saved = lines(&[
+            "# txtui",
+            "## Guides",
+            "<!-- include: notes.md -->",
+            "## Included heading",
+            "<!-- /include -->",
+            "After",
+        ]);
+        let sections = saved_sections(&saved, &RenderOptions::default(), &MarkdownStyle::default());
+        assert_eq!(
+            sections[&ContentListItem::Guides],
+            lines(&["<!-- include: notes.md -->", "After"])
+        );
+    }
+}
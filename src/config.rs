@@ -0,0 +1,461 @@
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::keymap::{Action, KeyContext};
+use crate::source;
+
+pub const PROJECT_CONFIG_FILE: &str = ".txtui.toml";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn keys(&self) -> Vec<String> {
+        match self {
+            KeyList::One(key) => vec![key.clone()],
+            KeyList::Many(keys) => keys.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeysConfig {
+    #[serde(default)]
+    pub navigation: HashMap<Action, KeyList>,
+    #[serde(default)]
+    pub editor: HashMap<Action, KeyList>,
+    #[serde(default)]
+    pub popup: HashMap<Action, KeyList>,
+}
+
+impl KeysConfig {
+    pub fn bindings(&self, context: KeyContext) -> &HashMap<Action, KeyList> {
+        match context {
+            KeyContext::Navigation => &self.navigation,
+            KeyContext::Editor => &self.editor,
+            KeyContext::Popup => &self.popup,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MarkdownConfig {
+    pub format_on_save: Option<bool>,
+    pub list_marker: Option<String>,
+    #[serde(default)]
+    pub rules: HashMap<String, bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpellConfig {
+    pub enabled: Option<bool>,
+    pub language: Option<String>,
+    pub dictionary_dir: Option<PathBuf>,
+    pub word_list: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TargetConfig {
+    pub min_words: Option<usize>,
+    pub max_words: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatsConfig {
+    pub words_per_minute: Option<usize>,
+    #[serde(default)]
+    pub targets: HashMap<String, TargetConfig>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputConfig {
+    pub dir: Option<PathBuf>,
+    pub file_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SourceConfig {
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplatesConfig {
+    pub dir: Option<PathBuf>,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SectionConfig {
+    pub heading: Option<String>,
+    pub label: Option<String>,
+    pub template: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DocumentConfig {
+    pub order: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SnippetConfig {
+    pub description: Option<String>,
+    pub sections: Option<Vec<String>>,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IncludesConfig {
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExportConfig {
+    pub formats: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    pub deny_warnings: Option<bool>,
+    #[serde(default)]
+    pub rules: HashMap<String, bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub border: Option<String>,
+    pub highlight: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ConfigFile {
+    #[serde(default)]
+    pub output: OutputConfig,
+    #[serde(default)]
+    pub source: SourceConfig,
+    #[serde(default)]
+    pub templates: TemplatesConfig,
+    #[serde(default)]
+    pub document: DocumentConfig,
+    #[serde(default)]
+    pub sections: HashMap<String, SectionConfig>,
+    #[serde(default)]
+    pub snippets: HashMap<String, SnippetConfig>,
+    #[serde(default)]
+    pub includes: IncludesConfig,
+    #[serde(default)]
+    pub export: ExportConfig,
+    #[serde(default)]
+    pub lint: LintConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    #[serde(default)]
+    pub keys: KeysConfig,
+    #[serde(default)]
+    pub markdown: MarkdownConfig,
+    #[serde(default)]
+    pub spell: SpellConfig,
+    #[serde(default)]
+    pub stats: StatsConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadedConfig {
+    pub path: PathBuf,
+    pub file: ConfigFile,
+}
+
+impl LoadedConfig {
+    pub fn resolve_path(&self, path: &Path) -> PathBuf {
+        match self.path.parent() {
+            Some(parent) if path.is_relative() && !parent.as_os_str().is_empty() => {
+                parent.join(path)
+            }
+            _ => path.to_path_buf(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    CommandLine,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+pub fn user_config_path() -> Option<PathBuf> {
+    if let Ok(config_home) = env::var("XDG_CONFIG_HOME") {
+        if !config_home.is_empty() {
+            return Some(Path::new(&config_home).join("txtui").join("config.toml"));
+        }
+    }
+    env::var("HOME")
+        .or_else(|_| env::var("USERPROFILE"))
+        .ok()
+        .map(|home| {
+            Path::new(&home)
+                .join(".config")
+                .join("txtui")
+                .join("config.toml")
+        })
+}
+
+pub fn read_config_file(path: &Path) -> Result<ConfigFile> {
+    let data = fs::read_to_string(path)
+        .wrap_err_with(|| format!("Unable to read config file {}", path.display()))?;
+    toml::from_str(&data).wrap_err_with(|| format!("Invalid config file {}", path.display()))
+}
+
+pub fn find_project_config(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+pub fn project_config_path() -> PathBuf {
+    env::current_dir()
+        .ok()
+        .and_then(|dir| find_project_config(&dir))
+        .unwrap_or_else(|| PathBuf::from(PROJECT_CONFIG_FILE))
+}
+
+pub fn update_config_file(path: &Path, updates: &[(&[&str], toml_edit::Value)]) -> Result<()> {
+    let data = if path.is_file() {
+        fs::read_to_string(path)
+            .wrap_err_with(|| format!("Unable to read config file {}", path.display()))?
+    } else {
+        String::new()
+    };
+    let mut document: toml_edit::DocumentMut = data
+        .parse()
+        .wrap_err_with(|| format!("Invalid config file {}", path.display()))?;
+    for (keys, value) in updates {
+        let Some((key, tables)) = keys.split_last() else {
+            continue;
+        };
+        let mut table: &mut dyn toml_edit::TableLike = document.as_table_mut();
+        let mut inline = false;
+        for name in tables {
+            if table.get(name).is_none() {
+                let item = if inline {
+                    toml_edit::Item::Value(toml_edit::InlineTable::new().into())
+                } else {
+                    toml_edit::Item::Table(toml_edit::Table::new())
+                };
+                table.insert(name, item);
+            }
+            let item = table.get_mut(name).expect("entry was just inserted");
+            inline = inline || item.is_inline_table();
+            table = item.as_table_like_mut().ok_or_else(|| {
+                eyre!(
+                    "{}: '{}' is not a table, unable to set {}",
+                    path.display(),
+                    name,
+                    keys.join(".")
+                )
+            })?;
+        }
+        table.insert(key, toml_edit::value(value.clone()));
+    }
+    source::write_atomic(path, &document.to_string())
+        .wrap_err_with(|| format!("Unable to write config file {}", path.display()))
+}
+
+pub fn setting<T>(configs: &[LoadedConfig], get: impl Fn(&LoadedConfig) -> Option<T>) -> Option<T> {
+    configs.iter().rev().find_map(get)
+}
+
+fn flatten(prefix: &str, table: &toml::Table, values: &mut Vec<(String, toml::Value)>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            toml::Value::Table(table) => flatten(&key, table, values),
+            value => values.push((key, value.clone())),
+        }
+    }
+}
+
+pub fn effective_values(
+    configs: &[LoadedConfig],
+    defaults: Vec<(String, toml::Value)>,
+    overrides: Vec<(String, toml::Value)>,
+) -> Result<BTreeMap<String, (toml::Value, Source)>> {
+    let mut values: BTreeMap<String, (toml::Value, Source)> = defaults
+        .into_iter()
+        .map(|(key, value)| (key, (value, Source::Default)))
+        .collect();
+    for config in configs {
+        let data = fs::read_to_string(&config.path)
+            .wrap_err_with(|| format!("Unable to read config file {}", config.path.display()))?;
+        let table: toml::Table = toml::from_str(&data)
+            .wrap_err_with(|| format!("Invalid config file {}", config.path.display()))?;
+        let mut file_values = Vec::new();
+        flatten("", &table, &mut file_values);
+        for (key, value) in file_values {
+            values.insert(key, (value, Source::File(config.path.clone())));
+        }
+    }
+    for (key, value) in overrides {
+        values.insert(key, (value, Source::CommandLine));
+    }
+    Ok(values)
+}
+
+pub fn load_configs() -> Result<Vec<LoadedConfig>> {
+    let project_config = env::current_dir()
+        .ok()
+        .and_then(|dir| find_project_config(&dir));
+    let candidates = [user_config_path(), project_config];
+    let mut configs = Vec::new();
+    for path in candidates.into_iter().flatten() {
+        if path.is_file() {
+            let file = read_config_file(&path)?;
+            configs.push(LoadedConfig { path, file });
+        }
+    }
+    Ok(configs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn test_project_config_is_found_in_parent_directories() {
+        let root = env::temp_dir().join(format!("txtui-config-{}", process::id()));
+        let nested = root.join("docs").join("guides");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            root.join(PROJECT_CONFIG_FILE),
+            "[output]\ndir = \"site\"\n[markdown.rules]\nlist-marker = false",
+        )
+        .unwrap();
+        let path = find_project_config(&nested);
+        let config = LoadedConfig {
+            path: root.join(PROJECT_CONFIG_FILE),
+            file: read_config_file(&root.join(PROJECT_CONFIG_FILE)).unwrap(),
+        };
+        let values = effective_values(
+            std::slice::from_ref(&config),
+            vec![
+                ("output.dir".to_string(), "txtui-output".into()),
+                ("output.file_name".to_string(), "README.md".into()),
+            ],
+            vec![("output.file_name".to_string(), "INDEX.md".into())],
+        )
+        .unwrap();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(path, Some(root.join(PROJECT_CONFIG_FILE)));
+        assert_eq!(
+            config.resolve_path(config.file.output.dir.as_ref().unwrap()),
+            root.join("site")
+        );
+        assert_eq!(
+            values["output.dir"],
+            ("site".into(), Source::File(config.path.clone()))
+        );
+        assert_eq!(
+            values["markdown.rules.list-marker"],
+            (false.into(), Source::File(config.path.clone()))
+        );
+        assert_eq!(
+            values["output.file_name"],
+            ("INDEX.md".into(), Source::CommandLine)
+        );
+    }
+
+    #[test]
+    fn test_config_file_is_updated_in_place() {
+        let path = env::temp_dir().join(format!("txtui-config-update-{}.toml", process::id()));
+        fs::write(
+            &path,
+            "# shared settings\n[sections.guides]\ntemplate = \"howto.md\"\n",
+        )
+        .unwrap();
+        let order: toml_edit::Array = ["reference", "guides"].into_iter().collect();
+        update_config_file(
+            &path,
+            &[
+                (&["document", "order"], order.into()),
+                (&["sections", "guides", "label"], "Recipes".into()),
+            ],
+        )
+        .unwrap();
+        let data = fs::read_to_string(&path).unwrap();
+        let file = read_config_file(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert!(data.starts_with("# shared settings\n"));
+        assert_eq!(
+            file.document.order,
+            Some(vec!["reference".to_string(), "guides".to_string()])
+        );
+        let guides = &file.sections["guides"];
+        assert_eq!(guides.label.as_deref(), Some("Recipes"));
+        assert_eq!(guides.template.as_deref(), Some("howto.md"));
+
+        fs::write(
+            &path,
+            "sections = { guides = { template = \"howto.md\" } }\n",
+        )
+        .unwrap();
+        update_config_file(
+            &path,
+            &[
+                (&["sections", "guides", "label"], "Recipes".into()),
+                (&["sections", "reference", "label"], "API".into()),
+            ],
+        )
+        .unwrap();
+        let file = read_config_file(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(file.sections["guides"].label.as_deref(), Some("Recipes"));
+        assert_eq!(
+            file.sections["guides"].template.as_deref(),
+            Some("howto.md")
+        );
+        assert_eq!(file.sections["reference"].label.as_deref(), Some("API"));
+    }
+}
@@ -1,14 +1,16 @@
-use color_eyre::eyre::Result;
 use ratatui::{
     layout::{Position, Rect},
-    style::{Color, Style},
-    widgets::{
-        Block, Borders, Clear, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
-        ScrollbarState,
-    },
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
-use std::{collections::HashMap, fs, path::Path};
+use std::collections::HashMap;
+
+use crate::front_matter::PageMetadata;
+use crate::sections::SectionDefinitions;
+use crate::templates::{self, TemplateRead};
+use crate::theme::Theme;
 
 #[derive(Hash, Eq, PartialEq, Clone, Debug)]
 pub enum ContentListItem {
@@ -19,15 +21,47 @@ pub enum ContentListItem {
     Reference,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub style: Style,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabStop {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+struct UndoSnapshot {
+    file_to_save: HashMap<ContentListItem, Vec<String>>,
+    cursor_index_x: usize,
+    cursor_index_y: usize,
+}
+
 pub struct Content {
     pub content_input: Vec<String>,
-    topic_content_map: HashMap<ContentListItem, String>,
+    definitions: SectionDefinitions,
+    pub theme: Theme,
     pub enable_insert_mode: bool,
+    pub show_cursor: bool,
     pub file_to_save: HashMap<ContentListItem, Vec<String>>,
+    pub front_matter: HashMap<ContentListItem, PageMetadata>,
     pub cursor_index_x: usize,
     pub cursor_index_y: usize,
     scroll_offset: usize,
     visible_height: usize,
+    area: Rect,
+    selected_index: usize,
+    undo_stack: Vec<UndoSnapshot>,
+    tab_stops: Vec<TabStop>,
+    active_tab_stop: Option<usize>,
+    tab_stop_selected: bool,
+    restored_cursor: Option<(usize, usize)>,
+    templates: HashMap<ContentListItem, TemplateRead>,
     pub vertical_scroll_state: ScrollbarState,
 }
 
@@ -39,26 +73,82 @@ impl Default for Content {
 
 impl Content {
     pub fn new() -> Self {
-        let mut topic_content_map = HashMap::new();
-        topic_content_map.insert(ContentListItem::ProjectName, "name.md".to_string());
-        topic_content_map.insert(ContentListItem::Tutorials, "tutorials.md".to_string());
-        topic_content_map.insert(ContentListItem::Guides, "guides.md".to_string());
-        topic_content_map.insert(ContentListItem::Explanation, "explanation.md".to_string());
-        topic_content_map.insert(ContentListItem::Reference, "reference.md".to_string());
-
-        Self {
+        Content::with_definitions(SectionDefinitions::default())
+    }
+
+    pub fn with_definitions(definitions: SectionDefinitions) -> Self {
+        let mut content = Self {
             content_input: Vec::new(),
-            topic_content_map,
+            definitions,
+            theme: Theme::default(),
             enable_insert_mode: false,
+            show_cursor: false,
             file_to_save: HashMap::new(),
+            front_matter: HashMap::new(),
             cursor_index_x: 0,
             cursor_index_y: 0,
             scroll_offset: 0,
             visible_height: 0,
+            area: Rect::default(),
+            selected_index: 0,
+            undo_stack: Vec::new(),
+            tab_stops: Vec::new(),
+            active_tab_stop: None,
+            tab_stop_selected: false,
+            restored_cursor: None,
+            templates: HashMap::new(),
             vertical_scroll_state: ScrollbarState::default(),
+        };
+        content.reload_templates();
+        content
+    }
+
+    pub fn reload_templates(&mut self) {
+        self.templates = Content::sections()
+            .into_iter()
+            .map(|section| {
+                let read = templates::read(&self.definitions, &section)
+                    .map_err(|error| format!("{:#}", error));
+                (section, read)
+            })
+            .collect();
+    }
+
+    pub fn template_read(&self, section: &ContentListItem) -> Option<&TemplateRead> {
+        self.templates.get(section)
+    }
+
+    fn template_lines(&self, section: &ContentListItem) -> Option<Vec<String>> {
+        match self.template_read(section)? {
+            Ok((lines, _)) => Some(lines.clone()),
+            Err(_) => None,
         }
     }
 
+    pub fn definitions(&self) -> &SectionDefinitions {
+        &self.definitions
+    }
+
+    pub fn set_definitions(&mut self, definitions: SectionDefinitions) {
+        self.definitions = definitions;
+        self.reload_templates();
+    }
+
+    pub fn sections() -> Vec<ContentListItem> {
+        (0..).map_while(Content::get_content_for_index).collect()
+    }
+
+    pub fn index_for_section(section: &ContentListItem) -> usize {
+        Content::sections()
+            .iter()
+            .position(|item| item == section)
+            .unwrap_or(0)
+    }
+
+    pub fn current_section(&self) -> Option<ContentListItem> {
+        Content::get_content_for_index(self.selected_index)
+    }
+
     fn get_content_for_index(index: usize) -> Option<ContentListItem> {
         match index {
             0 => Some(ContentListItem::ProjectName),
@@ -71,50 +161,185 @@ impl Content {
     }
 
     pub fn select_placeholder(&mut self, index: usize) {
+        self.restored_cursor = None;
+        self.selected_index = index;
         if let Some(selected_topic) = Content::get_content_for_index(index) {
             if let Some(saved_content) = self.file_to_save.get(&selected_topic) {
                 self.content_input = saved_content.clone();
-            } else if let Some(content) = self.topic_content_map.get(&selected_topic) {
-                if !self.enable_insert_mode {
-                    self.content_input = Content::read_placeholder_from_file(content)
-                        .unwrap_or_else(|_| vec!["empty".to_string()]);
-                }
+            } else if !self.enable_insert_mode {
+                self.content_input = self
+                    .template_lines(&selected_topic)
+                    .unwrap_or_else(|| vec!["empty".to_string()]);
             }
         }
     }
 
-    pub fn read_placeholder_from_file(file: &str) -> Result<Vec<String>> {
-        let dir_path = Path::new("templates");
-        let file_path = dir_path.join(file);
-        let data = fs::read_to_string(file_path).expect("Unable to read file");
-        let lines: Vec<String> = data.lines().map(|line| line.to_string()).collect();
-        Ok(lines)
-    }
-
     pub fn save_content_for_current_topic(&mut self, index: usize) {
+        self.selected_index = index;
         if let Some(selected_topic) = Content::get_content_for_index(index) {
             self.file_to_save
-                .insert(selected_topic.clone(), self.content_input.clone());
-            if let Some(content) = self.topic_content_map.get_mut(&selected_topic) {
-                *content = content.clone();
+                .insert(selected_topic, self.content_input.clone());
+        }
+    }
+
+    pub fn section_lines(&self, section: &ContentListItem) -> Vec<String> {
+        if let Some(saved_content) = self.file_to_save.get(section) {
+            return saved_content.clone();
+        }
+        self.template_lines(section).unwrap_or_default()
+    }
+
+    pub fn templates(&self) -> HashMap<ContentListItem, Vec<String>> {
+        Content::sections()
+            .into_iter()
+            .map(|section| {
+                let lines = self.template_lines(&section).unwrap_or_default();
+                (section, lines)
+            })
+            .collect()
+    }
+
+    pub fn edited_sections(&self) -> Vec<(ContentListItem, Vec<String>)> {
+        let current = self.current_section();
+        Content::sections()
+            .into_iter()
+            .filter_map(|section| {
+                if self.enable_insert_mode && current.as_ref() == Some(&section) {
+                    Some((section, self.content_input.clone()))
+                } else {
+                    let lines = self.file_to_save.get(&section)?.clone();
+                    Some((section, lines))
+                }
+            })
+            .collect()
+    }
+
+    pub fn document_sections(&self) -> Vec<(ContentListItem, Vec<String>)> {
+        let current = self.current_section();
+        self.definitions
+            .order()
+            .iter()
+            .map(|section| {
+                let lines = if self.enable_insert_mode && current.as_ref() == Some(section) {
+                    self.content_input.clone()
+                } else {
+                    self.section_lines(section)
+                };
+                (section.clone(), lines)
+            })
+            .collect()
+    }
+
+    pub fn replace_range(
+        &mut self,
+        section: &ContentListItem,
+        line: usize,
+        start: usize,
+        end: usize,
+        replacement: &str,
+    ) {
+        if !self.file_to_save.contains_key(section) {
+            let lines = self.section_lines(section);
+            self.file_to_save.insert(section.clone(), lines);
+        }
+        if let Some(lines) = self.file_to_save.get_mut(section) {
+            if let Some(text) = lines.get_mut(line) {
+                text.replace_range(start..end, replacement);
             }
         }
+        if self.current_section().as_ref() == Some(section) {
+            if let Some(text) = self.content_input.get_mut(line) {
+                text.replace_range(start..end, replacement);
+            }
+        }
+    }
+
+    pub fn set_section_lines(&mut self, section: &ContentListItem, lines: Vec<String>) {
+        self.push_undo();
+        self.write_section_lines(section, lines);
+    }
+
+    pub fn set_sections(&mut self, sections: Vec<(ContentListItem, Vec<String>)>) {
+        if sections.is_empty() {
+            return;
+        }
+        self.push_undo();
+        for (section, lines) in sections {
+            self.write_section_lines(&section, lines);
+        }
+    }
+
+    fn write_section_lines(&mut self, section: &ContentListItem, lines: Vec<String>) {
+        if self.current_section().as_ref() == Some(section) {
+            self.clear_tab_stops();
+            self.content_input = lines.clone();
+            self.cursor_index_y = self
+                .cursor_index_y
+                .min(self.content_input.len().saturating_sub(1));
+            let line_length = self
+                .content_input
+                .get(self.cursor_index_y)
+                .map(|line| line.len())
+                .unwrap_or(0);
+            self.cursor_index_x = self.cursor_index_x.min(line_length);
+        }
+        self.file_to_save.insert(section.clone(), lines);
+    }
+
+    pub fn push_undo(&mut self) {
+        if self.enable_insert_mode {
+            if let Some(section) = self.current_section() {
+                self.file_to_save
+                    .insert(section, self.content_input.clone());
+            }
+        }
+        self.undo_stack.push(UndoSnapshot {
+            file_to_save: self.file_to_save.clone(),
+            cursor_index_x: self.cursor_index_x,
+            cursor_index_y: self.cursor_index_y,
+        });
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(snapshot) = self.undo_stack.pop() {
+            self.clear_tab_stops();
+            self.file_to_save = snapshot.file_to_save;
+            if let Some(section) = self.current_section() {
+                self.content_input = self.section_lines(&section);
+            }
+            self.cursor_index_y = snapshot
+                .cursor_index_y
+                .min(self.content_input.len().saturating_sub(1));
+            let line_length = self
+                .content_input
+                .get(self.cursor_index_y)
+                .map(|line| line.len())
+                .unwrap_or(0);
+            self.cursor_index_x = snapshot.cursor_index_x.min(line_length);
+        }
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect, list_state: &ListState) {
+    pub fn render(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        selected_section: Option<usize>,
+        highlights: &[Highlight],
+    ) {
         self.visible_height = (area.height as usize).saturating_sub(2);
+        self.area = area;
 
         if !self.enable_insert_mode {
-            if let Some(selected_index) = list_state.selected() {
+            if let Some(selected_index) = selected_section {
                 self.select_placeholder(selected_index);
             }
-        } else if let Some(selected_index) = list_state.selected() {
+        } else if let Some(selected_index) = selected_section {
             self.save_content_for_current_topic(selected_index);
         }
 
         self.adjust_scroll();
 
-        if self.enable_insert_mode {
+        if self.enable_insert_mode || self.show_cursor {
             let cursor_y = self.cursor_index_y.saturating_sub(self.scroll_offset);
             if cursor_y < self.visible_height {
                 frame.set_cursor_position(Position::new(
@@ -126,21 +351,27 @@ impl Content {
 
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Red));
+            .border_style(Style::default().fg(self.theme.border));
 
         let inner_area = block.inner(area);
         frame.render_widget(Clear, inner_area);
 
-        let visible_content: Vec<&str> = self
+        let visible_content: Vec<Line> = self
             .content_input
             .iter()
+            .enumerate()
             .skip(self.scroll_offset)
             .take(self.visible_height)
-            .map(|s| s.as_str())
+            .map(|(index, line)| {
+                let line_highlights: Vec<&Highlight> = highlights
+                    .iter()
+                    .filter(|highlight| highlight.line == index)
+                    .collect();
+                Content::styled_line(line, line_highlights)
+            })
             .collect();
 
-        let content_str = visible_content.join("\n");
-        let content_paragraph = Paragraph::new(content_str).block(block);
+        let content_paragraph = Paragraph::new(visible_content).block(block);
         self.vertical_scroll_state = self
             .vertical_scroll_state
             .content_length(self.content_input.len())
@@ -157,9 +388,29 @@ impl Content {
         );
     }
 
+    fn styled_line<'a>(line: &'a str, mut highlights: Vec<&Highlight>) -> Line<'a> {
+        highlights.sort_by_key(|highlight| highlight.start);
+        let mut spans = Vec::new();
+        let mut position = 0;
+        for highlight in highlights {
+            let start = highlight.start.max(position);
+            let end = highlight.end.min(line.len());
+            if start >= end || !line.is_char_boundary(start) || !line.is_char_boundary(end) {
+                continue;
+            }
+            spans.push(Span::raw(&line[position..start]));
+            spans.push(Span::styled(&line[start..end], highlight.style));
+            position = end;
+        }
+        spans.push(Span::raw(&line[position..]));
+        Line::from(spans)
+    }
+
     fn adjust_scroll(&mut self) {
         if self.cursor_index_y >= self.scroll_offset + self.visible_height {
-            self.scroll_offset = self.cursor_index_y.saturating_sub(self.visible_height - 1);
+            self.scroll_offset = self
+                .cursor_index_y
+                .saturating_sub(self.visible_height.saturating_sub(1));
         } else if self.cursor_index_y < self.scroll_offset {
             self.scroll_offset = self.cursor_index_y;
         }
@@ -167,19 +418,110 @@ impl Content {
         self.scroll_offset = self.scroll_offset.min(max_scroll);
     }
 
+    fn inner_area(&self) -> Rect {
+        Block::default().borders(Borders::ALL).inner(self.area)
+    }
+
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        self.inner_area().contains(Position::new(column, row))
+    }
+
+    pub fn place_cursor(&mut self, column: u16, row: u16) {
+        if self.content_input.is_empty() || !self.contains(column, row) {
+            return;
+        }
+        let inner_area = self.inner_area();
+        let line_index = self.scroll_offset + (row - inner_area.y) as usize;
+        self.cursor_index_y = line_index.min(self.content_input.len() - 1);
+        let line_length = self.content_input[self.cursor_index_y].len();
+        self.cursor_index_x = ((column - inner_area.x) as usize).min(line_length);
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(lines);
+        self.keep_cursor_in_view();
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        let max_scroll = self.content_input.len().saturating_sub(self.visible_height);
+        self.scroll_offset = (self.scroll_offset + lines).min(max_scroll);
+        self.keep_cursor_in_view();
+    }
+
+    fn keep_cursor_in_view(&mut self) {
+        let last_visible = self.scroll_offset + self.visible_height.saturating_sub(1);
+        self.cursor_index_y = self
+            .cursor_index_y
+            .clamp(self.scroll_offset, last_visible)
+            .min(self.content_input.len().saturating_sub(1));
+        let line_length = self
+            .content_input
+            .get(self.cursor_index_y)
+            .map(|line| line.len())
+            .unwrap_or(0);
+        self.cursor_index_x = self.cursor_index_x.min(line_length);
+    }
+
+    pub fn jump_to_line(&mut self, line: usize) {
+        self.cursor_index_y = line.min(self.content_input.len().saturating_sub(1));
+        self.cursor_index_x = 0;
+        self.scroll_offset = self.cursor_index_y;
+    }
+
+    pub fn restore_cursor(&mut self, line: usize, column: usize) {
+        self.jump_to_line(line);
+        self.cursor_index_x = self
+            .content_input
+            .get(self.cursor_index_y)
+            .map_or(0, |text| {
+                let mut column = column.min(text.len());
+                while !text.is_char_boundary(column) {
+                    column -= 1;
+                }
+                column
+            });
+        self.restored_cursor = Some((self.cursor_index_y, self.cursor_index_x));
+    }
+
+    pub fn set_insert_mode(&mut self, enabled: bool) {
+        if !enabled {
+            self.clear_tab_stops();
+        }
+        self.enable_insert_mode = enabled;
+    }
+
     pub fn toggle_insert(&mut self) {
+        self.clear_tab_stops();
         self.enable_insert_mode = !self.enable_insert_mode;
-        self.cursor_index_y = 0;
-        self.cursor_index_x = 0;
+        let restored = self
+            .restored_cursor
+            .take()
+            .filter(|_| self.enable_insert_mode);
+        (self.cursor_index_y, self.cursor_index_x) = restored.unwrap_or((0, 0));
     }
 
     pub fn delete_char(&mut self) {
+        if self.take_selected_placeholder() {
+            return;
+        }
         if self.cursor_index_y < self.content_input.len() {
             let line = &mut self.content_input[self.cursor_index_y];
 
             if self.cursor_index_x > 0 {
                 line.remove(self.cursor_index_x - 1);
                 self.cursor_index_x -= 1;
+                for stop in self
+                    .tab_stops
+                    .iter_mut()
+                    .filter(|stop| stop.line == self.cursor_index_y)
+                {
+                    if stop.start > self.cursor_index_x {
+                        stop.start -= 1;
+                        stop.end -= 1;
+                    } else if stop.end > self.cursor_index_x {
+                        stop.end -= 1;
+                    }
+                }
             } else if self.cursor_index_y > 0 {
                 let current_line = self.content_input.remove(self.cursor_index_y);
                 self.cursor_index_y -= 1;
@@ -187,6 +529,15 @@ impl Content {
                     self.cursor_index_x = previous_line.len();
                     previous_line.push_str(&current_line);
                 }
+                for stop in &mut self.tab_stops {
+                    if stop.line == self.cursor_index_y + 1 {
+                        stop.line -= 1;
+                        stop.start += self.cursor_index_x;
+                        stop.end += self.cursor_index_x;
+                    } else if stop.line > self.cursor_index_y + 1 {
+                        stop.line -= 1;
+                    }
+                }
             }
         }
     }
@@ -263,16 +614,242 @@ impl Content {
     }
 
     pub fn insert_char(&mut self, ch: char) {
+        self.take_selected_placeholder();
         if self.cursor_index_y >= self.content_input.len() {
             self.content_input.push(String::new());
         }
         if let Some(line) = self.content_input.get_mut(self.cursor_index_y) {
             line.insert(self.cursor_index_x, ch);
+            let column = self.cursor_index_x;
+            for (index, stop) in self.tab_stops.iter_mut().enumerate() {
+                if stop.line != self.cursor_index_y {
+                    continue;
+                }
+                if self.active_tab_stop == Some(index) && (stop.start..=stop.end).contains(&column)
+                {
+                    stop.end += 1;
+                } else if stop.start >= column {
+                    stop.start += 1;
+                    stop.end += 1;
+                } else if stop.end > column {
+                    stop.end += 1;
+                }
+            }
             self.cursor_index_x += 1;
         }
     }
 
+    pub fn insert_snippet(&mut self, lines: &[String], tab_stops: &[TabStop]) {
+        if self.cursor_index_y >= self.content_input.len() {
+            self.content_input.push(String::new());
+            self.cursor_index_y = self.content_input.len() - 1;
+        }
+        let line = self.cursor_index_y;
+        let column = self.cursor_index_x.min(self.content_input[line].len());
+        self.insert_text(lines);
+        self.tab_stops = tab_stops
+            .iter()
+            .map(|stop| {
+                let offset = if stop.line == 0 { column } else { 0 };
+                TabStop {
+                    line: line + stop.line,
+                    start: stop.start + offset,
+                    end: stop.end + offset,
+                }
+            })
+            .collect();
+        self.next_tab_stop();
+    }
+
+    pub fn has_tab_stops(&self) -> bool {
+        !self.tab_stops.is_empty()
+    }
+
+    pub fn next_tab_stop(&mut self) {
+        let next = self.active_tab_stop.map_or(0, |index| index + 1);
+        if next >= self.tab_stops.len() {
+            self.clear_tab_stops();
+            return;
+        }
+        self.select_tab_stop(next);
+        let stop = self.tab_stops[next];
+        if next + 1 == self.tab_stops.len() && stop.start == stop.end {
+            self.clear_tab_stops();
+        }
+    }
+
+    pub fn previous_tab_stop(&mut self) {
+        if let Some(index) = self.active_tab_stop {
+            self.select_tab_stop(index.saturating_sub(1));
+        }
+    }
+
+    fn select_tab_stop(&mut self, index: usize) {
+        let Some(stop) = self.tab_stops.get(index).copied() else {
+            return;
+        };
+        self.active_tab_stop = Some(index);
+        self.cursor_index_y = stop.line.min(self.content_input.len().saturating_sub(1));
+        let line_length = self
+            .content_input
+            .get(self.cursor_index_y)
+            .map(|line| line.len())
+            .unwrap_or(0);
+        self.cursor_index_x = stop.start.min(line_length);
+        self.tab_stop_selected = stop.start < stop.end.min(line_length);
+        self.adjust_scroll();
+    }
+
+    fn clear_tab_stops(&mut self) {
+        self.tab_stops.clear();
+        self.active_tab_stop = None;
+        self.tab_stop_selected = false;
+    }
+
+    fn take_selected_placeholder(&mut self) -> bool {
+        if !std::mem::take(&mut self.tab_stop_selected) {
+            return false;
+        }
+        let Some(active) = self.active_tab_stop else {
+            return false;
+        };
+        let stop = self.tab_stops[active];
+        if (self.cursor_index_y, self.cursor_index_x) != (stop.line, stop.start) {
+            return false;
+        }
+        let Some(line) = self.content_input.get_mut(stop.line) else {
+            return false;
+        };
+        if stop.end > line.len() {
+            return false;
+        }
+        line.replace_range(stop.start..stop.end, "");
+        let removed = stop.end - stop.start;
+        for (index, other) in self.tab_stops.iter_mut().enumerate() {
+            if index == active {
+                other.end = other.start;
+            } else if other.line == stop.line && other.start >= stop.end {
+                other.start -= removed;
+                other.end -= removed;
+            }
+        }
+        true
+    }
+
+    pub fn tab_stop_highlights(&self, style: Style) -> Vec<Highlight> {
+        self.tab_stops
+            .iter()
+            .filter(|stop| stop.start < stop.end)
+            .map(|stop| Highlight {
+                line: stop.line,
+                start: stop.start,
+                end: stop.end,
+                style,
+            })
+            .collect()
+    }
+
+    pub fn text_range(&self, start: (usize, usize), end: (usize, usize)) -> Vec<String> {
+        let (start_line, start_column) = start;
+        let (end_line, end_column) = end;
+        let line_at = |index: usize| self.content_input.get(index).map(String::as_str);
+        if start_line == end_line {
+            let line = line_at(start_line).unwrap_or_default();
+            return vec![
+                line[start_column.min(line.len())..end_column.min(line.len())].to_string(),
+            ];
+        }
+        let first = line_at(start_line).unwrap_or_default();
+        let last = line_at(end_line).unwrap_or_default();
+        let mut text = vec![first[start_column.min(first.len())..].to_string()];
+        text.extend(
+            self.content_input
+                .iter()
+                .take(end_line)
+                .skip(start_line + 1)
+                .cloned(),
+        );
+        text.push(last[..end_column.min(last.len())].to_string());
+        text
+    }
+
+    pub fn delete_range(&mut self, start: (usize, usize), end: (usize, usize)) -> Vec<String> {
+        self.clear_tab_stops();
+        let removed = self.text_range(start, end);
+        let (start_line, start_column) = start;
+        let (end_line, end_column) = end;
+        if start_line < self.content_input.len() {
+            let tail = self
+                .content_input
+                .get(end_line)
+                .map(|line| line[end_column.min(line.len())..].to_string())
+                .unwrap_or_default();
+            let last_line = end_line.min(self.content_input.len() - 1);
+            self.content_input
+                .drain(start_line + 1..=last_line.max(start_line));
+            let line = &mut self.content_input[start_line];
+            line.truncate(start_column.min(line.len()));
+            line.push_str(&tail);
+        }
+        self.cursor_index_y = start_line;
+        self.cursor_index_x = start_column;
+        removed
+    }
+
+    pub fn delete_lines(&mut self, first: usize, last: usize) -> Vec<String> {
+        self.clear_tab_stops();
+        if first >= self.content_input.len() {
+            return Vec::new();
+        }
+        let last = last.min(self.content_input.len() - 1);
+        let removed: Vec<String> = self.content_input.drain(first..=last).collect();
+        if self.content_input.is_empty() {
+            self.content_input.push(String::new());
+        }
+        self.cursor_index_y = first.min(self.content_input.len() - 1);
+        self.cursor_index_x = 0;
+        removed
+    }
+
+    pub fn insert_lines(&mut self, index: usize, lines: &[String]) {
+        self.clear_tab_stops();
+        let index = index.min(self.content_input.len());
+        self.content_input
+            .splice(index..index, lines.iter().cloned());
+        self.cursor_index_y = index;
+        self.cursor_index_x = 0;
+    }
+
+    pub fn insert_text(&mut self, text: &[String]) {
+        self.clear_tab_stops();
+        let Some((last, rest)) = text.split_last() else {
+            return;
+        };
+        if self.cursor_index_y >= self.content_input.len() {
+            self.content_input.push(String::new());
+            self.cursor_index_y = self.content_input.len() - 1;
+        }
+        let line = &mut self.content_input[self.cursor_index_y];
+        let column = self.cursor_index_x.min(line.len());
+        let after_cursor = line.split_off(column);
+        if rest.is_empty() {
+            line.push_str(last);
+            line.push_str(&after_cursor);
+            self.cursor_index_x = column + last.len();
+            return;
+        }
+        line.push_str(&rest[0]);
+        let mut new_lines: Vec<String> = rest[1..].to_vec();
+        new_lines.push(format!("{}{}", last, after_cursor));
+        let insert_at = self.cursor_index_y + 1;
+        self.content_input
+            .splice(insert_at..insert_at, new_lines.iter().cloned());
+        self.cursor_index_y += new_lines.len();
+        self.cursor_index_x = last.len();
+    }
+
     pub fn handle_enter(&mut self) {
+        self.take_selected_placeholder();
         if self.cursor_index_y >= self.content_input.len() {
             self.content_input.push(String::new());
             self.cursor_index_y += 1;
@@ -285,6 +862,17 @@ impl Content {
         self.content_input[self.cursor_index_y] = before_cursor.to_string();
         self.content_input
             .insert(self.cursor_index_y + 1, after_cursor.to_string());
+        for stop in &mut self.tab_stops {
+            if stop.line == self.cursor_index_y && stop.start >= self.cursor_index_x {
+                stop.line += 1;
+                stop.start -= self.cursor_index_x;
+                stop.end -= self.cursor_index_x;
+            } else if stop.line == self.cursor_index_y {
+                stop.end = stop.end.min(self.cursor_index_x);
+            } else if stop.line > self.cursor_index_y {
+                stop.line += 1;
+            }
+        }
 
         self.cursor_index_y += 1;
         self.cursor_index_x = 0;
@@ -0,0 +1,114 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem},
+    Frame,
+};
+use std::{fmt, path::Path};
+
+use crate::content::{Content, ContentListItem, Highlight};
+use crate::links;
+use crate::lint::{self, LintRules};
+use crate::markdown::MarkdownStyle;
+use crate::placeholders;
+use crate::stats::StatsTargets;
+use crate::templates;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn style(&self) -> Style {
+        match self {
+            Severity::Warning => Style::default().fg(Color::Yellow),
+            Severity::Error => Style::default().fg(Color::Red),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub section: ContentListItem,
+    pub line: Option<usize>,
+    pub severity: Severity,
+    pub rule: &'static str,
+    pub message: String,
+}
+
+pub fn collect(
+    content: &Content,
+    base_dir: &Path,
+    markdown_style: &MarkdownStyle,
+    stats_targets: &StatsTargets,
+    lint_rules: &LintRules,
+) -> Vec<Diagnostic> {
+    let sections = content.document_sections();
+    let mut diagnostics = lint::lint_document(&sections);
+    diagnostics.extend(markdown_style.check_document(&sections));
+    diagnostics.extend(stats_targets.check_document(&sections));
+    diagnostics.extend(placeholders::check_content(content));
+    diagnostics.extend(links::check_content(content, base_dir));
+    diagnostics.extend(templates::check_content(content));
+    lint_rules.apply(diagnostics)
+}
+
+pub fn highlights(diagnostics: &[Diagnostic], section: &ContentListItem) -> Vec<Highlight> {
+    diagnostics
+        .iter()
+        .filter(|diagnostic| &diagnostic.section == section)
+        .filter_map(|diagnostic| {
+            Some(Highlight {
+                line: diagnostic.line?,
+                start: 0,
+                end: usize::MAX,
+                style: diagnostic
+                    .severity
+                    .style()
+                    .add_modifier(Modifier::UNDERLINED),
+            })
+        })
+        .collect()
+}
+
+pub fn render_panel(frame: &mut Frame, area: Rect, diagnostics: &[Diagnostic]) {
+    let items: Vec<ListItem> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let location = match diagnostic.line {
+                Some(line) => format!("{:?}:{}", diagnostic.section, line + 1),
+                None => format!("{:?}", diagnostic.section),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<8}", diagnostic.severity.to_string()),
+                    diagnostic.severity.style(),
+                ),
+                Span::raw(format!(
+                    "{} [{}] {}",
+                    location, diagnostic.rule, diagnostic.message
+                )),
+            ]))
+        })
+        .collect();
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue))
+            .title(format!(" Diagnostics ({}) ", diagnostics.len())),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(list, area);
+}
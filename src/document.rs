@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+
+use crate::content::ContentListItem;
+use crate::links;
+use crate::sections::SectionDefinitions;
+
+const SECTION_LEVEL: usize = 2;
+const MAX_HEADING_LEVEL: usize = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub toc_depth: Option<usize>,
+    pub normalize_headings: bool,
+    pub sections: SectionDefinitions,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            toc_depth: None,
+            normalize_headings: true,
+            sections: SectionDefinitions::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedSection {
+    pub section: ContentListItem,
+    pub first_line: usize,
+    pub lines: Vec<String>,
+}
+
+pub fn render(
+    file_to_save: &HashMap<ContentListItem, Vec<String>>,
+    options: &RenderOptions,
+) -> Vec<String> {
+    let sections: Vec<(ContentListItem, Vec<String>)> = options
+        .sections
+        .order()
+        .iter()
+        .filter_map(|section| {
+            let lines = file_to_save.get(section)?.clone();
+            Some((section.clone(), lines))
+        })
+        .collect();
+    render_sections(&sections, options)
+}
+
+pub fn render_sections(
+    sections: &[(ContentListItem, Vec<String>)],
+    options: &RenderOptions,
+) -> Vec<String> {
+    let section_lines = |wanted: &ContentListItem| {
+        sections
+            .iter()
+            .find(|(section, _)| section == wanted)
+            .map(|(_, lines)| lines)
+    };
+    let mut output = Vec::new();
+    if let Some(project_lines) = section_lines(&ContentListItem::ProjectName) {
+        output.extend(title_block(project_lines));
+    }
+    for section in options.sections.order() {
+        if section == &ContentListItem::ProjectName {
+            continue;
+        }
+        if let Some(lines) = section_lines(section) {
+            output.push(format!("## {}", options.sections.heading(section)));
+            if options.normalize_headings {
+                output.extend(normalize_headings(lines));
+            } else {
+                output.extend(lines.iter().cloned());
+            }
+            output.push(String::new());
+        }
+    }
+    if let Some(depth) = options.toc_depth {
+        let toc = table_of_contents(&output, depth);
+        if !toc.is_empty() {
+            let position = output
+                .iter()
+                .position(|line| line.starts_with("## "))
+                .unwrap_or(output.len());
+            output.splice(position..position, toc.into_iter().chain([String::new()]));
+        }
+    }
+    output
+}
+
+pub fn title_block(project_lines: &[String]) -> Vec<String> {
+    vec![
+        format!(
+            "# {}",
+            project_lines
+                .first()
+                .map(String::as_str)
+                .unwrap_or("Documentation")
+        ),
+        String::new(),
+        "This documentation follows the Diátaxis framework.".to_string(),
+        "Learn more at https://diataxis.fr/".to_string(),
+        String::new(),
+    ]
+}
+
+fn shift_under(lines: &[String], parent_level: usize) -> usize {
+    links::headings(lines)
+        .iter()
+        .map(|(_, level, _)| *level)
+        .min()
+        .map_or(0, |level| (parent_level + 1).saturating_sub(level))
+}
+
+pub fn heading_shift(lines: &[String]) -> usize {
+    shift_under(lines, SECTION_LEVEL)
+}
+
+pub fn shifted_level(level: usize, shift: usize) -> usize {
+    (level + shift).min(MAX_HEADING_LEVEL)
+}
+
+pub fn normalize_headings(lines: &[String]) -> Vec<String> {
+    nest_headings(lines, SECTION_LEVEL)
+}
+
+pub fn nest_headings(lines: &[String], parent_level: usize) -> Vec<String> {
+    let shift = shift_under(lines, parent_level);
+    let mut normalized = lines.to_vec();
+    if shift == 0 {
+        return normalized;
+    }
+    for (index, level, _) in links::headings(lines) {
+        let line = &lines[index];
+        let indent = line.len() - line.trim_start().len();
+        normalized[index] = format!(
+            "{}{}{}",
+            &line[..indent],
+            "#".repeat(shifted_level(level, shift)),
+            &line[indent + level..]
+        );
+    }
+    normalized
+}
+
+pub fn table_of_contents(lines: &[String], depth: usize) -> Vec<String> {
+    links::headings(lines)
+        .into_iter()
+        .zip(links::anchors(lines))
+        .filter(|((_, level, _), _)| (2..=depth).contains(level))
+        .map(|((_, level, title), anchor)| {
+            format!(
+                "{}- [{}](#{})",
+                "  ".repeat(level - 2),
+                links::heading_text(&title),
+                anchor
+            )
+        })
+        .collect()
+}
+
+pub fn parse(lines: &[String], definitions: &SectionDefinitions) -> Vec<ParsedSection> {
+    let mut sections: Vec<ParsedSection> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let section_start = line
+            .strip_prefix("## ")
+            .and_then(|title| definitions.section_for_heading(title))
+            .filter(|section| section != &ContentListItem::ProjectName);
+        if let Some(section) = section_start {
+            sections.push(ParsedSection {
+                section,
+                first_line: index + 1,
+                lines: Vec::new(),
+            });
+        } else if let Some(current) = sections.last_mut() {
+            current.lines.push(line.clone());
+        } else if let Some(title) = line.strip_prefix("# ") {
+            sections.push(ParsedSection {
+                section: ContentListItem::ProjectName,
+                first_line: index,
+                lines: vec![title.to_string()],
+            });
+        }
+    }
+    for parsed in sections.iter_mut() {
+        if parsed.section == ContentListItem::ProjectName {
+            parsed.lines.truncate(1);
+        } else if parsed.lines.last().is_some_and(|line| line.is_empty()) {
+            parsed.lines.pop();
+        }
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+
+    #[test]
+    fn test_render_orders_sections() {
+        let mut file_to_save = HashMap::new();
+        file_to_save.insert(ContentListItem::Reference, lines(&["ref"]));
+        file_to_save.insert(ContentListItem::Tutorials, lines(&["tut"]));
+        file_to_save.insert(ContentListItem::ProjectName, lines(&["txtui"]));
+
+        let output = render(&file_to_save, &RenderOptions::default());
+        assert_eq!(output[0], "# txtui");
+        assert_eq!(
+            output[5..].to_vec(),
+            lines(&["## Tutorials", "tut", "", "## Reference", "ref", ""])
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trips_render() {
+        let mut file_to_save = HashMap::new();
+        file_to_save.insert(ContentListItem::ProjectName, lines(&["txtui"]));
+        file_to_save.insert(
+            ContentListItem::Guides,
+            lines(&["## Not a section", "", "text"]),
+        );
+        file_to_save.insert(ContentListItem::Explanation, lines(&["why"]));
+
+        let options = RenderOptions {
+            normalize_headings: false,
+            ..RenderOptions::default()
+        };
+        let parsed = parse(&render(&file_to_save, &options), &options.sections);
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].lines, lines(&["txtui"]));
+        assert_eq!(parsed[1].section, ContentListItem::Guides);
+        assert_eq!(parsed[1].first_line, 6);
+        assert_eq!(parsed[1].lines, lines(&["## Not a section", "", "text"]));
+        assert_eq!(parsed[2].lines, lines(&["why"]));
+    }
+
+    #[test]
+    fn test_toc_lists_nested_headings_with_unique_anchors() {
+        let mut file_to_save = HashMap::new();
+        file_to_save.insert(ContentListItem::ProjectName, lines(&["txtui"]));
+        file_to_save.insert(
+            ContentListItem::Tutorials,
+            lines(&[
+                "### Setup",
+                "#### Objective",
+                "```",
+                "### not a heading",
+                "```",
+            ]),
+        );
+        file_to_save.insert(
+            ContentListItem::Guides,
+            lines(&["### [Setup](#setup)", "#### Objective", "##### Deep"]),
+        );
+
+        let output = render(
+            &file_to_save,
+            &RenderOptions {
+                toc_depth: Some(4),
+                ..RenderOptions::default()
+            },
+        );
+        assert_eq!(
+            output[5..13].to_vec(),
+            lines(&[
+                "- [Tutorials](#tutorials)",
+                "  - [Setup](#setup)",
+                "    - [Objective](#objective)",
+                "- [Guides](#guides)",
+                "  - [Setup](#setup-1)",
+                "    - [Objective](#objective-1)",
+                "",
+                "## Tutorials",
+            ])
+        );
+
+        let output = render(
+            &file_to_save,
+            &RenderOptions {
+                toc_depth: Some(2),
+                ..RenderOptions::default()
+            },
+        );
+        assert_eq!(
+            output[5..8].to_vec(),
+            lines(&["- [Tutorials](#tutorials)", "- [Guides](#guides)", ""])
+        );
+        assert_eq!(parse(&output, &SectionDefinitions::default()).len(), 3);
+    }
+
+    #[test]
+    fn test_body_headings_nest_under_section_heading() {
+        assert_eq!(
+            normalize_headings(&lines(&[
+                "# Setup",
+                "text",
+                "```",
+                "# comment",
+                "```",
+                "### Details"
+            ])),
+            lines(&[
+                "### Setup",
+                "text",
+                "```",
+                "# comment",
+                "```",
+                "##### Details"
+            ])
+        );
+        assert_eq!(
+            normalize_headings(&lines(&["#### Already nested", "###### Deep"])),
+            lines(&["#### Already nested", "###### Deep"])
+        );
+        assert_eq!(
+            normalize_headings(&lines(&["## Two", "###### Six"])),
+            lines(&["### Two", "###### Six"])
+        );
+    }
+}
@@ -1,22 +1,51 @@
 use copypasta::{ClipboardContext, ClipboardProvider};
-use crossterm::event::{self, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{
+    self, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use std::cell::RefCell;
-use std::fs::{self, File};
-use std::io::{self, Write};
-use std::path::Path;
+use std::io;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::time::Duration;
 
+use crate::config;
 use crate::content::{Content, ContentListItem};
+use crate::document::{self, RenderOptions};
+use crate::export::{Exporter, Exports};
+use crate::includes::Includes;
+use crate::keymap::{Action, KeyContext, Keymap};
+use crate::markdown::MarkdownStyle;
+use crate::placeholders;
 use crate::popup::{Popup, PopupButton};
 use crate::screen::Screen;
+use crate::search::{Search, SearchMode};
+use crate::sections::{section_key, SectionDefinitions};
+use crate::snippets::SnippetLibrary;
+use crate::source::{Cursor, DocumentSource};
+use crate::spell::{SpellChecker, SuggestionChoice};
+use crate::vim::{Vim, VimAction, VimMode};
+use crate::CliConfig;
+
+const SCROLL_LINES: usize = 3;
+
 pub struct EventHandler {
     pub should_quit: bool,
+    pub external_edit_requested: bool,
     screen: Rc<RefCell<Screen>>,
     popup: Rc<RefCell<Popup>>,
     content: Rc<RefCell<Content>>,
-    output_dir: String,
-    file_name: String,
+    search: Rc<RefCell<Search>>,
+    vim: Rc<RefCell<Vim>>,
+    spell: Rc<RefCell<SpellChecker>>,
+    keymap: Keymap,
+    snippets: SnippetLibrary,
+    includes: Includes,
+    exports: Exports,
+    source: DocumentSource,
+    project_config: PathBuf,
+    render_options: RenderOptions,
+    markdown_style: MarkdownStyle,
+    save_warned_for: Option<Vec<String>>,
 }
 
 impl EventHandler {
@@ -24,51 +53,160 @@ impl EventHandler {
         screen: Rc<RefCell<Screen>>,
         popup: Rc<RefCell<Popup>>,
         content: Rc<RefCell<Content>>,
-        output_dir: String,
-        file_name: String,
+        search: Rc<RefCell<Search>>,
+        vim: Rc<RefCell<Vim>>,
+        spell: Rc<RefCell<SpellChecker>>,
+        cli_config: &CliConfig,
     ) -> Self {
         Self {
             should_quit: false,
+            external_edit_requested: false,
             screen,
             popup,
             content,
-            output_dir,
-            file_name,
+            search,
+            vim,
+            spell,
+            keymap: cli_config.keymap.clone(),
+            snippets: cli_config.snippets.clone(),
+            includes: cli_config.includes.clone(),
+            exports: cli_config.exports.clone(),
+            source: cli_config.source.clone(),
+            project_config: cli_config.project_config.clone(),
+            render_options: cli_config.render_options.clone(),
+            markdown_style: cli_config.markdown_style.clone(),
+            save_warned_for: None,
         }
     }
 
-    pub fn listen_for_keyboard_events(&mut self) -> io::Result<()> {
+    pub fn listen_for_events(&mut self) -> io::Result<()> {
         if event::poll(Duration::from_millis(250))? {
-            if let event::Event::Key(key) = event::read()? {
-                if self.content.borrow().enable_insert_mode {
-                    self.handle_content_input(key)?;
-                } else if self.screen.borrow().show_popup {
-                    self.handle_popup_events(key)?
-                } else {
-                    self.handle_navigation_input(key)?;
+            match event::read()? {
+                event::Event::Key(key) => {
+                    self.screen.borrow_mut().status_message = None;
+                    if self.search.borrow().active {
+                        self.handle_search_input(key)?;
+                    } else if self.screen.borrow().pack_picker.active {
+                        self.handle_pack_picker_input(key);
+                    } else if self.screen.borrow().snippet_picker.active {
+                        self.handle_snippet_picker_input(key);
+                    } else if self.screen.borrow().front_matter_form.active {
+                        self.handle_front_matter_input(key);
+                    } else if self.screen.borrow().renaming.is_some() {
+                        self.handle_rename_input(key);
+                    } else if self.spell.borrow().suggestions.active {
+                        self.handle_suggestion_input(key);
+                    } else if self.screen.borrow().show_popup {
+                        self.handle_popup_events(key)?
+                    } else if self.vim.borrow().enabled {
+                        self.handle_vim_input(key)?;
+                    } else if self.content.borrow().enable_insert_mode {
+                        self.handle_content_input(key)?;
+                    } else {
+                        self.handle_navigation_input(key)?;
+                    }
                 }
+                event::Event::Mouse(mouse) => self.handle_mouse_event(mouse)?,
+                _ => {}
             }
         }
         Ok(())
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> io::Result<()> {
+        let picker_active = {
+            let screen = self.screen.borrow();
+            screen.pack_picker.active
+                || screen.snippet_picker.active
+                || screen.front_matter_form.active
+                || screen.renaming.is_some()
+        };
+        if self.search.borrow().active || picker_active {
+            return Ok(());
+        }
+        if self.screen.borrow().show_popup {
+            if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+                let button = self
+                    .popup
+                    .borrow_mut()
+                    .click_button(mouse.column, mouse.row);
+                if let Some(button) = button {
+                    self.activate_popup_button(button)?;
+                }
+            }
+            return Ok(());
+        }
+        let mut content = self.content.borrow_mut();
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let mut screen = self.screen.borrow_mut();
+                if let Some(index) = screen.item_at(mouse.column, mouse.row) {
+                    if content.enable_insert_mode {
+                        content.toggle_insert();
+                    }
+                    screen.select_row(index, &mut content);
+                } else if content.contains(mouse.column, mouse.row) {
+                    if !content.enable_insert_mode {
+                        content.toggle_insert();
+                    }
+                    content.place_cursor(mouse.column, mouse.row);
+                }
+            }
+            MouseEventKind::ScrollUp => content.scroll_up(SCROLL_LINES),
+            MouseEventKind::ScrollDown => content.scroll_down(SCROLL_LINES),
+            _ => {}
+        }
+        Ok(())
+    }
+
     pub fn handle_navigation_input(&mut self, key: KeyEvent) -> io::Result<()> {
-        match key.code {
-            KeyCode::Char('q') => {
+        let Some(action) = self.keymap.action(KeyContext::Navigation, key) else {
+            return Ok(());
+        };
+        if let Some(mode) = EventHandler::search_mode_for_action(action) {
+            self.open_search(mode);
+            return Ok(());
+        }
+        match action {
+            Action::Undo => self.content.borrow_mut().undo(),
+            Action::OpenInEditor => self.external_edit_requested = true,
+            Action::ToggleDiagnostics => self.screen.borrow_mut().toggle_diagnostics(),
+            Action::ToggleOutline => self.screen.borrow_mut().toggle_outline(),
+            Action::ToggleStats => self.screen.borrow_mut().toggle_stats(),
+            Action::ToggleSpellCheck => self.toggle_spell_check(),
+            Action::EditFrontMatter => self.open_front_matter_form(),
+            Action::MoveSectionUp => self.move_section(true),
+            Action::MoveSectionDown => self.move_section(false),
+            Action::RenameSection => self.start_rename(),
+            Action::Quit => {
                 self.should_quit = true;
             }
-            KeyCode::Char('s') => {
-                self.save_to_file()?;
+            Action::Save => {
+                self.request_save()?;
+            }
+            Action::EditSection => {
+                let mut content = self.content.borrow_mut();
+                content.toggle_insert();
+                if let Some(line) = self.screen.borrow().selected_heading_line() {
+                    content.jump_to_line(line);
+                }
             }
-            KeyCode::Char('i') => self.content.borrow_mut().toggle_insert(),
-            KeyCode::Esc => {
+            Action::ExpandSection => self
+                .screen
+                .borrow_mut()
+                .expand(&mut self.content.borrow_mut()),
+            Action::CollapseSection => self
+                .screen
+                .borrow_mut()
+                .collapse(&mut self.content.borrow_mut()),
+            Action::ExitPrompt => {
                 self.screen.borrow_mut().toggle_popup();
             }
-            KeyCode::Down => self
+            Action::NextSection => self
                 .screen
                 .borrow_mut()
                 .next(&mut self.content.borrow_mut()),
-            KeyCode::Up => self
+            Action::PreviousSection => self
                 .screen
                 .borrow_mut()
                 .previous(&mut self.content.borrow_mut()),
@@ -78,9 +216,31 @@ impl EventHandler {
     }
 
     fn handle_content_input(&mut self, key: KeyEvent) -> io::Result<()> {
+        let action = self.keymap.action(KeyContext::Editor, key);
+        if let Some(mode) = action.and_then(EventHandler::search_mode_for_action) {
+            self.open_search(mode);
+            return Ok(());
+        }
+        if action == Some(Action::ToggleSpellCheck) {
+            self.toggle_spell_check();
+            return Ok(());
+        }
+        if action == Some(Action::SpellSuggestions) {
+            self.open_suggestions();
+            return Ok(());
+        }
+        if action == Some(Action::InsertSnippet) {
+            self.open_snippet_picker();
+            return Ok(());
+        }
         let mut content = self.content.borrow_mut();
-        match key.code {
-            KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+        match action {
+            Some(Action::Undo) => content.undo(),
+            Some(Action::OpenInEditor) => self.external_edit_requested = true,
+            Some(Action::ToggleDiagnostics) => self.screen.borrow_mut().toggle_diagnostics(),
+            Some(Action::ToggleOutline) => self.screen.borrow_mut().toggle_outline(),
+            Some(Action::ToggleStats) => self.screen.borrow_mut().toggle_stats(),
+            Some(Action::Paste) => {
                 if let Ok(mut ctx) = ClipboardContext::new() {
                     if let Ok(contents) = ctx.get_contents() {
                         for c in contents.chars() {
@@ -93,86 +253,663 @@ impl EventHandler {
                     }
                 }
             }
-            KeyCode::Char(c) => content.insert_char(c),
-            KeyCode::Enter => content.handle_enter(),
-            KeyCode::Esc => content.toggle_insert(),
-            KeyCode::Backspace => content.delete_char(),
-            KeyCode::Left => content.move_cursor_left(),
-            KeyCode::Right => content.move_cursor_right(),
-            KeyCode::Up => content.move_cursor_up(),
-            KeyCode::Down => content.move_cursor_down(),
-            KeyCode::F(1) => content.scroll_to_top(),
-            KeyCode::F(2) => content.scroll_to_bottom(),
-            _ => {}
+            Some(Action::NewLine) => content.handle_enter(),
+            Some(Action::NextTabStop) => content.next_tab_stop(),
+            Some(Action::PreviousTabStop) => content.previous_tab_stop(),
+            Some(Action::ExitInsert) => content.toggle_insert(),
+            Some(Action::DeleteBackward) => content.delete_char(),
+            Some(Action::CursorLeft) => content.move_cursor_left(),
+            Some(Action::CursorRight) => content.move_cursor_right(),
+            Some(Action::CursorUp) => content.move_cursor_up(),
+            Some(Action::CursorDown) => content.move_cursor_down(),
+            Some(Action::ScrollTop) => content.scroll_to_top(),
+            Some(Action::ScrollBottom) => content.scroll_to_bottom(),
+            Some(_) => {}
+            None => {
+                if let KeyCode::Char(c) = key.code {
+                    if !key.modifiers.contains(KeyModifiers::CONTROL) {
+                        content.insert_char(c);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_vim_input(&mut self, key: KeyEvent) -> io::Result<()> {
+        let action = self.keymap.action(KeyContext::Editor, key);
+        if let Some(mode) = action.and_then(EventHandler::search_mode_for_action) {
+            self.vim.borrow_mut().reset();
+            self.open_search(mode);
+            return Ok(());
+        }
+        if action == Some(Action::OpenInEditor) {
+            self.vim.borrow_mut().reset();
+            self.external_edit_requested = true;
+            return Ok(());
+        }
+        if action == Some(Action::ToggleDiagnostics) {
+            self.screen.borrow_mut().toggle_diagnostics();
+            return Ok(());
+        }
+        if action == Some(Action::ToggleOutline) {
+            self.screen.borrow_mut().toggle_outline();
+            return Ok(());
+        }
+        if action == Some(Action::ToggleStats) {
+            self.screen.borrow_mut().toggle_stats();
+            return Ok(());
+        }
+        if action == Some(Action::ToggleSpellCheck) {
+            self.toggle_spell_check();
+            return Ok(());
+        }
+        if action == Some(Action::SpellSuggestions) {
+            self.vim.borrow_mut().reset();
+            self.open_suggestions();
+            return Ok(());
+        }
+        if action == Some(Action::InsertSnippet) {
+            self.vim.borrow_mut().reset();
+            self.open_snippet_picker();
+            return Ok(());
+        }
+        if matches!(
+            action,
+            Some(Action::NextTabStop) | Some(Action::PreviousTabStop)
+        ) && self.content.borrow().has_tab_stops()
+        {
+            let mut content = self.content.borrow_mut();
+            if action == Some(Action::NextTabStop) {
+                content.next_tab_stop();
+            } else {
+                content.previous_tab_stop();
+            }
+            let mut vim = self.vim.borrow_mut();
+            vim.reset();
+            vim.mode = VimMode::Insert;
+            return Ok(());
+        }
+        let mut content = self.content.borrow_mut();
+        let viewed = (!content.enable_insert_mode).then(|| content.content_input.clone());
+        let action = self.vim.borrow_mut().handle_key(key, &mut content);
+        if let Some(viewed) = viewed {
+            if content.content_input != viewed || self.vim.borrow().mode == VimMode::Insert {
+                content.set_insert_mode(true);
+            }
+        }
+        drop(content);
+        match action {
+            VimAction::None => {}
+            VimAction::Save => {
+                self.request_save()?;
+            }
+            VimAction::Quit => self.screen.borrow_mut().toggle_popup(),
+            VimAction::ForceQuit => self.should_quit = true,
+            VimAction::SaveAndQuit => {
+                self.should_quit = self.request_save()?;
+            }
+            VimAction::NextSection => self.switch_section(true),
+            VimAction::PreviousSection => self.switch_section(false),
         }
         Ok(())
     }
 
+    fn switch_section(&mut self, forward: bool) {
+        let mut content = self.content.borrow_mut();
+        let mut screen = self.screen.borrow_mut();
+        content.set_insert_mode(false);
+        if forward {
+            screen.next(&mut content);
+        } else {
+            screen.previous(&mut content);
+        }
+        content.cursor_index_y = 0;
+        content.cursor_index_x = 0;
+    }
+
     fn handle_popup_events(&mut self, key: KeyEvent) -> io::Result<()> {
-        match key.code {
-            KeyCode::Esc => {
+        match self.keymap.action(KeyContext::Popup, key) {
+            Some(Action::CancelPopup) => {
                 self.screen.borrow_mut().toggle_popup();
             }
-            KeyCode::Right => {
+            Some(Action::NextButton) => {
                 self.popup.borrow_mut().next_button();
             }
-            KeyCode::Left => {
+            Some(Action::PreviousButton) => {
                 self.popup.borrow_mut().previous_button();
             }
-            KeyCode::Enter => match self.popup.borrow().select_button() {
-                PopupButton::Cancel => {
-                    self.screen.borrow_mut().toggle_popup();
-                }
-                PopupButton::ExitWithoutSaving => {
-                    self.should_quit = true;
+            Some(Action::ConfirmButton) => {
+                let button = self.popup.borrow().select_button();
+                self.activate_popup_button(button)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn search_mode_for_action(action: Action) -> Option<SearchMode> {
+        match action {
+            Action::Find => Some(SearchMode::Find),
+            Action::FindAll => Some(SearchMode::Project),
+            Action::Replace => Some(SearchMode::Replace),
+            _ => None,
+        }
+    }
+
+    fn open_search(&mut self, mode: SearchMode) {
+        self.content.borrow_mut().set_insert_mode(false);
+        self.search.borrow_mut().open(mode);
+        self.refresh_search();
+    }
+
+    fn refresh_search(&mut self) {
+        let mut search = self.search.borrow_mut();
+        let content = self.content.borrow();
+        let sections = if search.all_sections {
+            content.definitions().order().to_vec()
+        } else {
+            content.current_section().into_iter().collect()
+        };
+        let mut matches = Vec::new();
+        for section in &sections {
+            match search.find_in_lines(section, &content.section_lines(section)) {
+                Ok(found) => matches.extend(found),
+                Err(error) => {
+                    search.set_error(error);
+                    return;
                 }
-                PopupButton::ExitWithSave => {
-                    self.save_to_file()?;
-                    self.should_quit = true;
+            }
+        }
+        if search.all_sections {
+            search.set_matches(matches, 0, 0);
+        } else {
+            search.set_matches(matches, content.cursor_index_y, content.cursor_index_x);
+        }
+        drop(content);
+        drop(search);
+        self.jump_to_current_match();
+    }
+
+    fn jump_to_current_match(&mut self) {
+        let Some(found) = self.search.borrow().current_match().cloned() else {
+            return;
+        };
+        let mut content = self.content.borrow_mut();
+        if content.current_section().as_ref() != Some(&found.section) {
+            self.screen
+                .borrow_mut()
+                .select(Content::index_for_section(&found.section), &mut content);
+        }
+        content.cursor_index_y = found.line;
+        content.cursor_index_x = found.start;
+    }
+
+    fn handle_search_input(&mut self, key: KeyEvent) -> io::Result<()> {
+        if self.search.borrow().confirming {
+            return self.handle_replace_confirmation(key);
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.search.borrow_mut().close(),
+            KeyCode::Enter => self.accept_search(),
+            KeyCode::Tab => self.search.borrow_mut().toggle_field(),
+            KeyCode::Down => {
+                self.search.borrow_mut().next();
+                self.jump_to_current_match();
+            }
+            KeyCode::Up => {
+                self.search.borrow_mut().previous();
+                self.jump_to_current_match();
+            }
+            KeyCode::Char('n') if ctrl => {
+                self.search.borrow_mut().next();
+                self.jump_to_current_match();
+            }
+            KeyCode::Char('p') if ctrl => {
+                self.search.borrow_mut().previous();
+                self.jump_to_current_match();
+            }
+            KeyCode::Char('e') if ctrl => {
+                self.search.borrow_mut().toggle_regex();
+                self.refresh_search();
+            }
+            KeyCode::Char('a') if ctrl => {
+                self.search.borrow_mut().toggle_scope();
+                self.refresh_search();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.search.borrow_mut().insert_char(c);
+                self.refresh_search();
+            }
+            KeyCode::Backspace => {
+                self.search.borrow_mut().delete_char();
+                self.refresh_search();
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn accept_search(&mut self) {
+        let mut search = self.search.borrow_mut();
+        if search.matches.is_empty() {
+            return;
+        }
+        if search.mode == SearchMode::Replace {
+            search.current = 0;
+            search.confirming = true;
+            drop(search);
+            self.jump_to_current_match();
+        } else {
+            search.close();
+            drop(search);
+            self.content.borrow_mut().set_insert_mode(true);
+        }
+    }
+
+    fn handle_replace_confirmation(&mut self, key: KeyEvent) -> io::Result<()> {
+        match key.code {
+            KeyCode::Char('y') => self.replace_current_match(),
+            KeyCode::Char('n') => self.search.borrow_mut().skip_current(),
+            KeyCode::Char('a') => {
+                while !self.search.borrow().is_finished() {
+                    self.replace_current_match();
                 }
-            },
+            }
+            KeyCode::Char('q') | KeyCode::Esc => self.search.borrow_mut().close(),
             _ => {}
         }
+        if self.search.borrow().is_finished() {
+            self.search.borrow_mut().close();
+        } else {
+            self.jump_to_current_match();
+        }
         Ok(())
     }
 
-    fn save_to_file(&self) -> io::Result<()> {
-        let dir_path = Path::new(&self.output_dir);
-        let file_path = dir_path.join(&self.file_name);
-
-        if !dir_path.exists() {
-            fs::create_dir_all(dir_path)?;
-        }
-        let mut file = File::create(file_path)?;
-        if let Some(project_lines) = self
-            .content
-            .borrow()
-            .file_to_save
-            .get(&ContentListItem::ProjectName)
-        {
-            writeln!(
-                file,
-                "# {}",
-                project_lines
-                    .first()
-                    .unwrap_or(&"Documentation".to_string())
-            )?;
-            writeln!(file, "\nThis documentation follows the Diátaxis framework.")?;
-            writeln!(file, "Learn more at https://diataxis.fr/")?;
-            writeln!(file)?;
+    fn replace_current_match(&mut self) {
+        let mut search = self.search.borrow_mut();
+        let Some(found) = search.current_match().cloned() else {
+            return;
+        };
+        let mut content = self.content.borrow_mut();
+        if search.replaced_count == 0 {
+            content.push_undo();
+        }
+        content.replace_range(
+            &found.section,
+            found.line,
+            found.start,
+            found.end,
+            &found.replacement,
+        );
+        search.complete_replacement();
+    }
+
+    fn toggle_spell_check(&mut self) {
+        let message = self.spell.borrow_mut().toggle();
+        self.screen.borrow_mut().status_message = message;
+    }
+
+    fn open_suggestions(&mut self) {
+        let content = self.content.borrow();
+        let mut spell = self.spell.borrow_mut();
+        let found = spell.misspelling_at(
+            &content.content_input,
+            content.cursor_index_y,
+            content.cursor_index_x,
+        );
+        match found {
+            Some(found) if spell.enabled => {
+                let suggestions = spell.suggest(&found.word);
+                spell.suggestions.open(found, suggestions);
+            }
+            _ if !spell.enabled => {
+                self.screen.borrow_mut().status_message = Some("Spell checking is off".to_string());
+            }
+            _ => {
+                self.screen.borrow_mut().status_message =
+                    Some("No misspelling under the cursor".to_string());
+            }
+        }
+    }
+
+    fn open_snippet_picker(&mut self) {
+        let Some(section) = self.content.borrow().current_section() else {
+            return;
+        };
+        let snippets = self.snippets.for_section(&section);
+        let mut screen = self.screen.borrow_mut();
+        if snippets.is_empty() {
+            screen.status_message = Some(format!(
+                "No snippets for {}",
+                self.render_options.sections.label(&section)
+            ));
+            return;
+        }
+        screen.snippet_picker.open(snippets);
+    }
+
+    fn handle_snippet_picker_input(&mut self, key: KeyEvent) {
+        let mut screen = self.screen.borrow_mut();
+        match key.code {
+            KeyCode::Esc => screen.snippet_picker.close(),
+            KeyCode::Down | KeyCode::Tab => screen.snippet_picker.next(),
+            KeyCode::Up | KeyCode::BackTab => screen.snippet_picker.previous(),
+            KeyCode::Backspace => screen.snippet_picker.delete_char(),
+            KeyCode::Enter => {
+                let snippet = screen.snippet_picker.selected().cloned();
+                screen.snippet_picker.close();
+                let Some(snippet) = snippet else {
+                    return;
+                };
+                let (lines, tab_stops) = snippet.expand();
+                let mut content = self.content.borrow_mut();
+                content.push_undo();
+                content.set_insert_mode(true);
+                content.insert_snippet(&lines, &tab_stops);
+                if self.vim.borrow().enabled {
+                    self.vim.borrow_mut().mode = VimMode::Insert;
+                }
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                screen.snippet_picker.insert_char(c)
+            }
+            _ => {}
+        }
+    }
+
+    fn selected_section(&self) -> Option<ContentListItem> {
+        let index = self.screen.borrow().selected_section()?;
+        Content::sections().get(index).cloned()
+    }
+
+    fn apply_section_definitions(&mut self, definitions: SectionDefinitions) {
+        self.render_options.sections = definitions.clone();
+        self.content.borrow_mut().set_definitions(definitions);
+        self.screen
+            .borrow_mut()
+            .set_render_options(self.render_options.clone());
+    }
+
+    fn save_project_config(&mut self, updates: &[(&[&str], toml_edit::Value)]) {
+        let message = match config::update_config_file(&self.project_config, updates) {
+            Ok(()) => format!("Saved to {}", self.project_config.display()),
+            Err(error) => error.to_string(),
+        };
+        self.screen.borrow_mut().status_message = Some(message);
+    }
+
+    fn move_section(&mut self, up: bool) {
+        let Some(section) = self.selected_section() else {
+            return;
+        };
+        let mut definitions = self.render_options.sections.clone();
+        if !definitions.move_section(&section, up) {
+            return;
+        }
+        let order: toml_edit::Array = definitions.order_keys().into_iter().collect();
+        self.apply_section_definitions(definitions);
+        self.screen.borrow_mut().select(
+            Content::index_for_section(&section),
+            &mut self.content.borrow_mut(),
+        );
+        self.save_project_config(&[(&["document", "order"], order.into())]);
+    }
+
+    fn start_rename(&mut self) {
+        let section = self.selected_section();
+        let mut screen = self.screen.borrow_mut();
+        match section {
+            Some(ContentListItem::ProjectName) => {
+                screen.status_message =
+                    Some("The project name is edited in its section, not renamed".to_string())
+            }
+            Some(section) => {
+                screen.renaming = Some(self.render_options.sections.label(&section).to_string())
+            }
+            None => {}
+        }
+    }
+
+    fn handle_rename_input(&mut self, key: KeyEvent) {
+        let mut screen = self.screen.borrow_mut();
+        let Some(name) = screen.renaming.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => screen.renaming = None,
+            KeyCode::Backspace => {
+                name.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => name.push(c),
+            KeyCode::Enter => {
+                let name = screen.renaming.take().unwrap_or_default();
+                drop(screen);
+                let Some(section) = self.selected_section() else {
+                    return;
+                };
+                let mut definitions = self.render_options.sections.clone();
+                if let Err(error) = definitions.rename(&section, &name) {
+                    self.screen.borrow_mut().status_message = Some(error.to_string());
+                    return;
+                }
+                let key = section_key(&section);
+                let name = definitions.label(&section).to_string();
+                self.apply_section_definitions(definitions);
+                self.save_project_config(&[
+                    (&["sections", key, "heading"], name.as_str().into()),
+                    (&["sections", key, "label"], name.as_str().into()),
+                ]);
+            }
+            _ => {}
         }
+    }
+
+    fn open_front_matter_form(&mut self) {
         let content = self.content.borrow();
-        for (section, lines) in &content.file_to_save {
-            if section != &ContentListItem::ProjectName {
-                writeln!(file, "## {:?}", section)?;
+        let Some(section) = content.current_section() else {
+            return;
+        };
+        let metadata = content
+            .front_matter
+            .get(&section)
+            .cloned()
+            .unwrap_or_default();
+        let label = self.render_options.sections.label(&section);
+        self.screen
+            .borrow_mut()
+            .front_matter_form
+            .open(section.clone(), label, &metadata);
+    }
 
-                for line in lines {
-                    writeln!(file, "{}", line)?;
+    fn handle_front_matter_input(&mut self, key: KeyEvent) {
+        let mut screen = self.screen.borrow_mut();
+        match key.code {
+            KeyCode::Esc => screen.front_matter_form.close(),
+            KeyCode::Down | KeyCode::Tab => screen.front_matter_form.next_field(),
+            KeyCode::Up | KeyCode::BackTab => screen.front_matter_form.previous_field(),
+            KeyCode::Backspace => screen.front_matter_form.delete_char(),
+            KeyCode::Enter => {
+                let metadata = match screen.front_matter_form.submit() {
+                    Ok(metadata) => metadata,
+                    Err(error) => {
+                        screen.front_matter_form.error = Some(error.to_string());
+                        return;
+                    }
+                };
+                let Some(section) = screen.front_matter_form.section.clone() else {
+                    return;
+                };
+                screen.front_matter_form.close();
+                let mut content = self.content.borrow_mut();
+                if metadata.is_empty() {
+                    content.front_matter.remove(&section);
+                } else {
+                    content.front_matter.insert(section, metadata);
                 }
+                if !self.exports.formats.contains(&Exporter::Pages) {
+                    screen.status_message = Some(
+                        "Front matter is stored with the document and only written by the pages exporter"
+                            .to_string(),
+                    );
+                }
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                screen.front_matter_form.insert_char(c)
+            }
+            _ => {}
+        }
+    }
 
-                writeln!(file)?;
+    fn handle_pack_picker_input(&mut self, key: KeyEvent) {
+        let mut screen = self.screen.borrow_mut();
+        match key.code {
+            KeyCode::Esc => screen.pack_picker.close(),
+            KeyCode::Down | KeyCode::Tab => screen.pack_picker.next(),
+            KeyCode::Up | KeyCode::BackTab => screen.pack_picker.previous(),
+            KeyCode::Enter => {
+                let Some(pack) = screen.pack_picker.selected().cloned() else {
+                    screen.pack_picker.close();
+                    return;
+                };
+                screen.pack_picker.close();
+                let definitions = &self.render_options.sections;
+                screen.status_message = Some(match pack.write_templates(definitions, false) {
+                    Ok(written) => format!(
+                        "Wrote {} template(s) from the {} pack to {}",
+                        written.iter().filter(|(_, written)| *written).count(),
+                        pack.name,
+                        definitions.template_dir.display()
+                    ),
+                    Err(error) => error.to_string(),
+                });
+                let mut content = self.content.borrow_mut();
+                content.reload_templates();
+                if let Some(index) = screen.selected_section() {
+                    screen.select(index, &mut content);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_suggestion_input(&mut self, key: KeyEvent) {
+        let mut spell = self.spell.borrow_mut();
+        match key.code {
+            KeyCode::Esc => spell.suggestions.close(),
+            KeyCode::Down | KeyCode::Tab => spell.suggestions.next(),
+            KeyCode::Up | KeyCode::BackTab => spell.suggestions.previous(),
+            KeyCode::Enter => {
+                let Some(found) = spell.suggestions.misspelling.clone() else {
+                    spell.suggestions.close();
+                    return;
+                };
+                match spell.suggestions.choice() {
+                    SuggestionChoice::Replace(replacement) => {
+                        let mut content = self.content.borrow_mut();
+                        if let Some(section) = content.current_section() {
+                            content.push_undo();
+                            content.replace_range(
+                                &section,
+                                found.line,
+                                found.start,
+                                found.end,
+                                &replacement,
+                            );
+                            content.cursor_index_x = found.start + replacement.len();
+                        }
+                    }
+                    SuggestionChoice::AddToWordList => {
+                        if let Err(error) = spell.add_word(&found.word) {
+                            self.screen.borrow_mut().status_message = Some(error.to_string());
+                        }
+                    }
+                }
+                spell.suggestions.close();
+            }
+            _ => {}
+        }
+    }
+
+    fn activate_popup_button(&mut self, button: PopupButton) -> io::Result<()> {
+        match button {
+            PopupButton::Cancel => {
+                self.screen.borrow_mut().toggle_popup();
+            }
+            PopupButton::ExitWithoutSaving => {
+                self.should_quit = true;
+            }
+            PopupButton::ExitWithSave => {
+                self.should_quit = self.request_save()?;
+                if !self.should_quit {
+                    self.screen.borrow_mut().toggle_popup();
+                }
             }
         }
         Ok(())
     }
+
+    fn request_save(&mut self) -> io::Result<bool> {
+        if self.markdown_style.format_on_save {
+            let mut content = self.content.borrow_mut();
+            let formatted: Vec<_> = content
+                .edited_sections()
+                .into_iter()
+                .filter_map(|(section, lines)| {
+                    let formatted_lines = self.markdown_style.format_section(&lines);
+                    (formatted_lines != lines).then_some((section, formatted_lines))
+                })
+                .collect();
+            content.set_sections(formatted);
+        }
+        let (document, problems) = {
+            let content = self.content.borrow();
+            (
+                document::render(&content.file_to_save, &self.render_options),
+                placeholders::check_content(&content),
+            )
+        };
+        if !problems.is_empty() && self.save_warned_for.as_ref() != Some(&document) {
+            let mut screen = self.screen.borrow_mut();
+            screen.status_message = Some(format!(
+                "{} template placeholder problem(s) found - save again to write anyway",
+                problems.len()
+            ));
+            screen.show_diagnostics = true;
+            self.save_warned_for = Some(document);
+            return Ok(false);
+        }
+        self.save_warned_for = None;
+        self.save_to_file()?;
+        Ok(true)
+    }
+
+    fn save_to_file(&self) -> io::Result<()> {
+        let content = self.content.borrow();
+        let cursor = content.current_section().map(|section| Cursor {
+            section,
+            line: content.cursor_index_y,
+            column: content.cursor_index_x,
+        });
+        self.source.save(
+            &content.file_to_save,
+            &content.front_matter,
+            cursor.as_ref(),
+        )?;
+        let (sections, include_errors) = self.includes.expand_sections(&content.file_to_save);
+        if let Some((_, error)) = include_errors.first() {
+            self.screen.borrow_mut().status_message = Some(format!(
+                "{} include problem(s) found - {}",
+                include_errors.len(),
+                error.message
+            ));
+        }
+        Exports::write(&self.exports.outputs(
+            &sections,
+            &content.front_matter,
+            &self.render_options,
+        ))
+    }
 }
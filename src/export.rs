@@ -0,0 +1,289 @@
+use color_eyre::eyre::{eyre, Result};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::config::LoadedConfig;
+use crate::content::{Content, ContentListItem};
+use crate::document::{self, RenderOptions};
+use crate::front_matter::PageMetadata;
+use crate::sections::section_key;
+
+pub const DEFAULT_OUTPUT_DIR: &str = "txtui-output";
+pub const DEFAULT_FILE_NAME: &str = "README.md";
+pub const PAGES_INDEX: &str = "index.md";
+pub const EXPORTERS: [&str; 2] = ["readme", "pages"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exporter {
+    Readme,
+    Pages,
+}
+
+impl Exporter {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Exporter::Readme => "readme",
+            Exporter::Pages => "pages",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exports {
+    pub output_dir: PathBuf,
+    pub file_name: String,
+    pub formats: Vec<Exporter>,
+}
+
+impl Default for Exports {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from(DEFAULT_OUTPUT_DIR),
+            file_name: DEFAULT_FILE_NAME.to_string(),
+            formats: vec![Exporter::Readme],
+        }
+    }
+}
+
+pub fn page_file(section: &ContentListItem) -> String {
+    match section {
+        ContentListItem::ProjectName => PAGES_INDEX.to_string(),
+        section => format!("{}.md", section_key(section)),
+    }
+}
+
+fn with_front_matter(
+    front_matter: &HashMap<ContentListItem, PageMetadata>,
+    section: &ContentListItem,
+    lines: Vec<String>,
+) -> Vec<String> {
+    let mut page = front_matter
+        .get(section)
+        .map(PageMetadata::to_yaml)
+        .unwrap_or_default();
+    page.extend(lines);
+    page
+}
+
+fn render_pages(
+    sections: &[(ContentListItem, Vec<String>)],
+    front_matter: &HashMap<ContentListItem, PageMetadata>,
+    options: &RenderOptions,
+) -> Vec<(String, Vec<String>)> {
+    let mut pages = Vec::new();
+    let mut index = sections
+        .iter()
+        .find(|(section, _)| section == &ContentListItem::ProjectName)
+        .map(|(_, lines)| document::title_block(lines))
+        .unwrap_or_default();
+    for (section, lines) in sections {
+        if section == &ContentListItem::ProjectName {
+            continue;
+        }
+        let heading = options.sections.heading(section);
+        index.push(format!("- [{}]({})", heading, page_file(section)));
+        let mut page = vec![format!("# {}", heading), String::new()];
+        if options.normalize_headings {
+            page.extend(document::nest_headings(lines, 1));
+        } else {
+            page.extend(lines.iter().cloned());
+        }
+        pages.push((
+            page_file(section),
+            with_front_matter(front_matter, section, page),
+        ));
+    }
+    pages.insert(
+        0,
+        (
+            PAGES_INDEX.to_string(),
+            with_front_matter(front_matter, &ContentListItem::ProjectName, index),
+        ),
+    );
+    pages
+}
+
+impl Exports {
+    pub fn from_configs(
+        configs: &[LoadedConfig],
+        output_dir: Option<PathBuf>,
+        file_name: Option<String>,
+    ) -> Result<Self> {
+        let mut exports = Exports::default();
+        for config in configs {
+            if let Some(dir) = &config.file.output.dir {
+                exports.output_dir = config.resolve_path(dir);
+            }
+            if let Some(file_name) = &config.file.output.file_name {
+                exports.file_name = file_name.clone();
+            }
+            if let Some(formats) = &config.file.export.formats {
+                exports.formats.clear();
+                for format in formats {
+                    let exporter = match format.as_str() {
+                        "readme" => Exporter::Readme,
+                        "pages" => Exporter::Pages,
+                        _ => {
+                            return Err(eyre!(
+                                "{}: unknown exporter '{}' in [export] formats (available: {})",
+                                config.path.display(),
+                                format,
+                                EXPORTERS.join(", ")
+                            ))
+                        }
+                    };
+                    if !exports.formats.contains(&exporter) {
+                        exports.formats.push(exporter);
+                    }
+                }
+                if !exports.formats.contains(&Exporter::Readme) {
+                    return Err(eyre!(
+                        "{}: [export] formats must include 'readme' - saving, lint, verify and build all use the README",
+                        config.path.display()
+                    ));
+                }
+            }
+        }
+        if let Some(dir) = output_dir {
+            exports.output_dir = dir;
+        }
+        if let Some(file_name) = file_name {
+            exports.file_name = file_name;
+        }
+        let page_files: Vec<String> = Content::sections().iter().map(page_file).collect();
+        if exports.formats.contains(&Exporter::Pages) && page_files.contains(&exports.file_name) {
+            return Err(eyre!(
+                "output file name '{}' collides with a page written by the pages exporter",
+                exports.file_name
+            ));
+        }
+        Ok(exports)
+    }
+
+    pub fn readme_path(&self) -> PathBuf {
+        self.output_dir.join(&self.file_name)
+    }
+
+    pub fn outputs(
+        &self,
+        file_to_save: &HashMap<ContentListItem, Vec<String>>,
+        front_matter: &HashMap<ContentListItem, PageMetadata>,
+        options: &RenderOptions,
+    ) -> Vec<(PathBuf, Vec<String>)> {
+        let sections: Vec<(ContentListItem, Vec<String>)> = options
+            .sections
+            .order()
+            .iter()
+            .filter_map(|section| Some((section.clone(), file_to_save.get(section)?.clone())))
+            .collect();
+        let mut outputs = Vec::new();
+        for format in &self.formats {
+            match format {
+                Exporter::Readme => outputs.push((
+                    self.readme_path(),
+                    document::render_sections(&sections, options),
+                )),
+                Exporter::Pages => outputs.extend(
+                    render_pages(&sections, front_matter, options)
+                        .into_iter()
+                        .map(|(file, lines)| (self.output_dir.join(file), lines)),
+                ),
+            }
+        }
+        outputs
+    }
+
+    pub fn write(outputs: &[(PathBuf, Vec<String>)]) -> io::Result<()> {
+        for (path, lines) in outputs {
+            if let Some(dir) = path.parent().filter(|dir| !dir.exists()) {
+                fs::create_dir_all(dir)?;
+            }
+            let mut file = File::create(path)?;
+            for line in lines {
+                writeln!(file, "{}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFile;
+    use crate::test_support::lines;
+
+    #[test]
+    fn test_pages_exporter_writes_one_file_per_section() {
+        let exports = Exports {
+            formats: vec![Exporter::Readme, Exporter::Pages],
+            ..Exports::default()
+        };
+        let file_to_save = HashMap::from([
+            (ContentListItem::ProjectName, lines(&["txtui"])),
+            (ContentListItem::Guides, lines(&["# Build", "text"])),
+        ]);
+        let front_matter = HashMap::from([(
+            ContentListItem::Guides,
+            PageMetadata {
+                weight: Some(2),
+                ..PageMetadata::default()
+            },
+        )]);
+        let outputs = exports.outputs(&file_to_save, &front_matter, &RenderOptions::default());
+        let files: Vec<PathBuf> = outputs.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("txtui-output/README.md"),
+                PathBuf::from("txtui-output/index.md"),
+                PathBuf::from("txtui-output/guides.md"),
+            ]
+        );
+        assert!(outputs[1].1.contains(&"- [Guides](guides.md)".to_string()));
+        assert!(!outputs[0].1.contains(&"weight: 2".to_string()));
+        assert_eq!(
+            outputs[2].1,
+            lines(&[
+                "---",
+                "weight: 2",
+                "---",
+                "# Guides",
+                "",
+                "## Build",
+                "text"
+            ])
+        );
+    }
+
+    #[test]
+    fn test_formats_must_keep_the_readme() {
+        let config = |toml: &str| LoadedConfig {
+            path: PathBuf::from(".txtui.toml"),
+            file: toml::from_str::<ConfigFile>(toml).unwrap(),
+        };
+        let exports = Exports::from_configs(
+            &[config(
+                "[export]\nformats = [\"readme\", \"pages\", \"pages\"]",
+            )],
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(exports.formats, vec![Exporter::Readme, Exporter::Pages]);
+        for formats in ["[]", "[\"pages\"]"] {
+            let toml = format!("[export]\nformats = {}", formats);
+            assert!(Exports::from_configs(&[config(&toml)], None, None).is_err());
+        }
+        let pages = [config(
+            "[export]\nformats = [\"pages\", \"readme\", \"pages\"]",
+        )];
+        assert!(Exports::from_configs(&pages, None, Some("index.md".to_string())).is_err());
+        let exports = Exports::from_configs(&pages, None, Some("docs.md".to_string())).unwrap();
+        assert_eq!(exports.formats, vec![Exporter::Pages, Exporter::Readme]);
+    }
+}
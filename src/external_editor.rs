@@ -0,0 +1,127 @@
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use std::{env, fs, io::Write, path::Path, process::Command};
+
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+#[cfg(windows)]
+const COMMAND_NOT_FOUND: i32 = 9009;
+#[cfg(not(windows))]
+const COMMAND_NOT_FOUND: i32 = 127;
+
+pub fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+pub fn edit_lines(name: &str, lines: &[String]) -> Result<Option<Vec<String>>> {
+    edit_lines_with(&editor_command(), name, lines)
+}
+
+#[cfg(not(windows))]
+fn editor_process(command: &str, file_path: &Path) -> Command {
+    let mut process = Command::new("sh");
+    process
+        .arg("-c")
+        .arg(format!("{} \"$1\"", command))
+        .arg("sh")
+        .arg(file_path);
+    process
+}
+
+#[cfg(windows)]
+fn editor_process(command: &str, file_path: &Path) -> Command {
+    let mut process = Command::new("cmd");
+    process
+        .arg("/C")
+        .arg(format!("{} \"{}\"", command, file_path.display()));
+    process
+}
+
+fn edit_lines_with(command: &str, name: &str, lines: &[String]) -> Result<Option<Vec<String>>> {
+    if command.trim().is_empty() {
+        return Err(eyre!("No editor configured in $VISUAL or $EDITOR"));
+    }
+    let mut file = tempfile::Builder::new()
+        .prefix(&format!("txtui-{}-", name))
+        .suffix(".md")
+        .tempfile()
+        .wrap_err("Unable to create a temporary file for the editor")?;
+    file.write_all((lines.join("\n") + "\n").as_bytes())
+        .wrap_err_with(|| format!("Unable to write {}", file.path().display()))?;
+    let file_path = file.into_temp_path();
+
+    let status = editor_process(command, &file_path)
+        .status()
+        .wrap_err_with(|| format!("Unable to launch editor '{}'", command))
+        .and_then(|status| match status.code() {
+            Some(COMMAND_NOT_FOUND) => Err(eyre!("Unable to launch editor '{}'", command)),
+            _ => Ok(status),
+        });
+    let result = match status {
+        Ok(status) if status.success() => fs::read_to_string(&file_path)
+            .map(|data| {
+                let mut edited: Vec<String> = data.lines().map(|line| line.to_string()).collect();
+                if edited.is_empty() {
+                    edited.push(String::new());
+                }
+                Some(edited)
+            })
+            .wrap_err_with(|| format!("Unable to read {}", file_path.display())),
+        Ok(_) => Ok(None),
+        Err(error) => Err(error),
+    };
+    let _ = file_path.close();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(windows))]
+    const EMPTYING_EDITOR: &str = ": >";
+    #[cfg(windows)]
+    const EMPTYING_EDITOR: &str = "type nul >";
+
+    #[test]
+    fn test_edited_text_is_read_back() {
+        let lines = vec!["alpha".to_string(), "beta".to_string()];
+        let edited = edit_lines_with("echo gamma>", "test-edit", &lines).unwrap();
+        assert_eq!(edited, Some(vec!["gamma".to_string()]));
+
+        let emptied = edit_lines_with(EMPTYING_EDITOR, "test-empty", &lines).unwrap();
+        assert_eq!(emptied, Some(vec![String::new()]));
+    }
+
+    #[test]
+    fn test_failed_editor_keeps_text() {
+        let lines = vec!["alpha".to_string()];
+        assert_eq!(
+            edit_lines_with("exit 1", "test-fail", &lines).unwrap(),
+            None
+        );
+        assert!(edit_lines_with("txtui-missing-editor", "test-missing", &lines).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_quoted_editor_path_with_spaces() {
+        let dir = env::temp_dir().join(format!("txtui-editor dir-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("my editor");
+        fs::write(
+            &script,
+            "#!/bin/sh\n[ \"$1\" = -w ] && echo waited > \"$2\"\n",
+        )
+        .unwrap();
+        let command = format!("sh \"{}\" -w", script.display());
+        let edited = edit_lines_with(&command, "test-quoted", &["alpha".to_string()]).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(edited, Some(vec!["waited".to_string()]));
+    }
+}
@@ -0,0 +1,265 @@
+use color_eyre::eyre::{eyre, Result};
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::content::ContentListItem;
+use crate::templates::FRONT_MATTER_DELIMITER;
+
+pub const FIELDS: [(&str, &str); 5] = [
+    ("title", "Title"),
+    ("description", "Description"),
+    ("tags", "Tags"),
+    ("weight", "Weight"),
+    ("sidebar_position", "Sidebar position"),
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PageMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sidebar_position: Option<i64>,
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl PageMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == PageMetadata::default()
+    }
+
+    pub fn to_yaml(&self) -> Vec<String> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let mut lines = vec![FRONT_MATTER_DELIMITER.to_string()];
+        if let Some(title) = &self.title {
+            lines.push(format!("title: {}", quote(title)));
+        }
+        if let Some(description) = &self.description {
+            lines.push(format!("description: {}", quote(description)));
+        }
+        if !self.tags.is_empty() {
+            lines.push("tags:".to_string());
+            lines.extend(self.tags.iter().map(|tag| format!("  - {}", quote(tag))));
+        }
+        if let Some(weight) = self.weight {
+            lines.push(format!("weight: {}", weight));
+        }
+        if let Some(position) = self.sidebar_position {
+            lines.push(format!("sidebar_position: {}", position));
+        }
+        lines.push(FRONT_MATTER_DELIMITER.to_string());
+        lines
+    }
+
+    fn values(&self) -> [String; 5] {
+        [
+            self.title.clone().unwrap_or_default(),
+            self.description.clone().unwrap_or_default(),
+            self.tags.join(", "),
+            self.weight
+                .map(|weight| weight.to_string())
+                .unwrap_or_default(),
+            self.sidebar_position
+                .map(|position| position.to_string())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+fn text_value(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+fn number_value(label: &str, value: &str) -> Result<Option<i64>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| eyre!("{} must be a whole number, got '{}'", label, value))
+}
+
+#[derive(Debug, Default)]
+pub struct FrontMatterForm {
+    pub active: bool,
+    pub section: Option<ContentListItem>,
+    pub error: Option<String>,
+    title: String,
+    values: [String; 5],
+    focused: usize,
+}
+
+impl FrontMatterForm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, section: ContentListItem, title: &str, metadata: &PageMetadata) {
+        self.active = true;
+        self.section = Some(section);
+        self.error = None;
+        self.title = title.to_string();
+        self.values = metadata.values();
+        self.focused = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.section = None;
+        self.error = None;
+    }
+
+    pub fn next_field(&mut self) {
+        self.focused = (self.focused + 1) % FIELDS.len();
+    }
+
+    pub fn previous_field(&mut self) {
+        self.focused = (self.focused + FIELDS.len() - 1) % FIELDS.len();
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.values[self.focused].push(c);
+    }
+
+    pub fn delete_char(&mut self) {
+        self.values[self.focused].pop();
+    }
+
+    pub fn submit(&self) -> Result<PageMetadata> {
+        let [title, description, tags, weight, sidebar_position] = &self.values;
+        Ok(PageMetadata {
+            title: text_value(title),
+            description: text_value(description),
+            tags: tags.split(',').filter_map(text_value).collect(),
+            weight: number_value(FIELDS[3].1, weight)?,
+            sidebar_position: number_value(FIELDS[4].1, sidebar_position)?,
+        })
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let height = (FIELDS.len() as u16 + 4).min(area.height);
+        let [popup_area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(Flex::Center)
+            .areas(popup_area);
+        let label_width = FIELDS
+            .iter()
+            .map(|(_, label)| label.len())
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let mut lines: Vec<Line> = FIELDS
+            .iter()
+            .zip(&self.values)
+            .enumerate()
+            .map(|(index, ((_, label), value))| {
+                let label_style = if index == self.focused {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Gray)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:>width$}", format!("{}: ", label), width = label_width),
+                        label_style,
+                    ),
+                    Span::raw(value.as_str()),
+                ])
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(match &self.error {
+            Some(error) => Line::styled(error.as_str(), Style::default().fg(Color::Red)),
+            None => Line::styled(
+                "Tab - next field, Enter - save, ESC - cancel, tags are comma separated",
+                Style::default().fg(Color::Gray),
+            ),
+        });
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue))
+            .title(format!(" Front matter - {} ", self.title));
+        let inner_area = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+        frame.set_cursor_position(Position::new(
+            inner_area.x + (label_width + self.values[self.focused].len()) as u16,
+            inner_area.y + self.focused as u16,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_values_become_front_matter() {
+        let mut form = FrontMatterForm::new();
+        form.open(
+            ContentListItem::Guides,
+            "How-to Guides",
+            &PageMetadata {
+                title: Some("Guides".to_string()),
+                ..PageMetadata::default()
+            },
+        );
+        form.next_field();
+        "Tasks \"done\" right"
+            .chars()
+            .for_each(|c| form.insert_char(c));
+        form.next_field();
+        "howto, , cli ".chars().for_each(|c| form.insert_char(c));
+        form.previous_field();
+        form.previous_field();
+        form.previous_field();
+        "x".chars().for_each(|c| form.insert_char(c));
+        assert_eq!(
+            form.submit().unwrap_err().to_string(),
+            "Sidebar position must be a whole number, got 'x'"
+        );
+        form.delete_char();
+        form.insert_char('2');
+
+        let metadata = form.submit().unwrap();
+        assert_eq!(metadata.tags, vec!["howto", "cli"]);
+        assert_eq!(
+            metadata.to_yaml(),
+            vec![
+                "---",
+                "title: \"Guides\"",
+                "description: \"Tasks \\\"done\\\" right\"",
+                "tags:",
+                "  - \"howto\"",
+                "  - \"cli\"",
+                "sidebar_position: 2",
+                "---",
+            ]
+        );
+        assert!(PageMetadata::default().to_yaml().is_empty());
+    }
+}
@@ -0,0 +1,399 @@
+use color_eyre::eyre::Result;
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use crate::config::LoadedConfig;
+use crate::content::{Content, ContentListItem};
+use crate::markdown;
+
+pub const END_MARKER: &str = "<!-- /include -->";
+pub const REGION_START: &str = "ANCHOR:";
+pub const REGION_END: &str = "ANCHOR_END:";
+
+static DIRECTIVE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*<!--\s*include:\s*([^#\s]+)(?:#(\S+))?\s*-->\s*$")
+        .expect("valid include regex")
+});
+static LINE_RANGE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^L(\d+)(?:-L?(\d+))?$").expect("valid line range regex"));
+
+const LANGUAGES: [(&str, &str); 14] = [
+    ("rs", "rust"),
+    ("py", "python"),
+    ("sh", "sh"),
+    ("bash", "bash"),
+    ("toml", "toml"),
+    ("json", "json"),
+    ("yaml", "yaml"),
+    ("yml", "yaml"),
+    ("js", "javascript"),
+    ("ts", "typescript"),
+    ("go", "go"),
+    ("c", "c"),
+    ("html", "html"),
+    ("sql", "sql"),
+];
+const TEXT_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncludeError {
+    pub line: usize,
+    pub message: String,
+}
+
+pub type SectionErrors = Vec<(ContentListItem, IncludeError)>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Includes {
+    pub base_dir: PathBuf,
+}
+
+impl Default for Includes {
+    fn default() -> Self {
+        Self {
+            base_dir: PathBuf::from("."),
+        }
+    }
+}
+
+fn directive(line: &str) -> Option<(String, Option<String>)> {
+    let captures = DIRECTIVE.captures(line)?;
+    Some((
+        captures[1].to_string(),
+        captures
+            .get(2)
+            .map(|fragment| fragment.as_str().to_string()),
+    ))
+}
+
+fn region(lines: &[&str], name: &str) -> Option<Vec<String>> {
+    let is_marker = |line: &str, marker: &str| {
+        line.split_once(marker)
+            .is_some_and(|(_, rest)| rest.split_whitespace().next() == Some(name))
+    };
+    let start = lines
+        .iter()
+        .position(|line| is_marker(line, REGION_START))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| is_marker(line, REGION_END))?;
+    Some(
+        lines[start + 1..end]
+            .iter()
+            .filter(|line| !line.contains(REGION_START) && !line.contains(REGION_END))
+            .map(|line| line.to_string())
+            .collect(),
+    )
+}
+
+fn is_marker(line: &str) -> bool {
+    directive(line).is_some() || line.trim() == END_MARKER
+}
+
+fn fence(lines: &[String]) -> String {
+    let longest = lines
+        .iter()
+        .flat_map(|line| line.split(|c| c != '`'))
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+pub fn collapse(lines: &[String]) -> Vec<String> {
+    let fenced = markdown::fenced_lines(lines);
+    let mut collapsed = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        collapsed.push(lines[index].clone());
+        if !fenced[index] && directive(&lines[index]).is_some() {
+            let rest = &lines[index + 1..];
+            let rest_fenced = markdown::fenced_lines(rest);
+            let block_end = rest
+                .iter()
+                .zip(rest_fenced)
+                .position(|(line, fenced)| !fenced && is_marker(line))
+                .filter(|offset| rest[*offset].trim() == END_MARKER);
+            if let Some(offset) = block_end {
+                index += offset + 1;
+            }
+        }
+        index += 1;
+    }
+    collapsed
+}
+
+fn check_text(path: &str, included: &[String]) -> Result<(), String> {
+    if markdown::has_unclosed_fence(included) {
+        return Err(format!("{}: included text leaves a code fence open", path));
+    }
+    let fenced = markdown::fenced_lines(included);
+    if let Some(index) = included
+        .iter()
+        .zip(fenced)
+        .position(|(line, fenced)| !fenced && is_marker(line))
+    {
+        return Err(format!(
+            "{}: included text has an include marker on line {} outside a code block",
+            path,
+            index + 1
+        ));
+    }
+    Ok(())
+}
+
+impl Includes {
+    pub fn from_configs(configs: &[LoadedConfig]) -> Result<Self> {
+        let mut includes = Includes::default();
+        for config in configs {
+            if let Some(dir) = &config.file.includes.dir {
+                includes.base_dir = config.resolve_path(dir);
+            }
+        }
+        Ok(includes)
+    }
+
+    fn read(&self, path: &str, fragment: Option<&str>) -> Result<Vec<String>, String> {
+        let target = match fragment {
+            Some(fragment) => format!("{}#{}", path, fragment),
+            None => path.to_string(),
+        };
+        let unreadable =
+            |error: std::io::Error| format!("{}: unable to read included file ({})", target, error);
+        let base_dir = self.base_dir.canonicalize().map_err(unreadable)?;
+        let file = self
+            .base_dir
+            .join(path)
+            .canonicalize()
+            .map_err(unreadable)?;
+        if !file.starts_with(&base_dir) {
+            return Err(format!(
+                "{}: included files must be inside {}",
+                target,
+                self.base_dir.display()
+            ));
+        }
+        let data = fs::read_to_string(file).map_err(unreadable)?;
+        let lines: Vec<&str> = data.lines().collect();
+        let Some(fragment) = fragment else {
+            return Ok(lines.iter().map(|line| line.to_string()).collect());
+        };
+        let Some(range) = LINE_RANGE.captures(fragment) else {
+            return region(&lines, fragment)
+                .ok_or_else(|| format!("{}: region '{}' no longer exists", target, fragment));
+        };
+        let first: usize = range[1].parse().unwrap_or(0);
+        let last: usize = range
+            .get(2)
+            .and_then(|last| last.as_str().parse().ok())
+            .unwrap_or(first);
+        if first == 0 || last < first {
+            return Err(format!("{}: invalid line range", target));
+        }
+        if last > lines.len() {
+            return Err(format!("{}: file has only {} line(s)", target, lines.len()));
+        }
+        Ok(lines[first - 1..last]
+            .iter()
+            .map(|line| line.to_string())
+            .collect())
+    }
+
+    pub fn expand(&self, lines: &[String]) -> (Vec<String>, Vec<IncludeError>) {
+        let mut expanded = Vec::new();
+        let mut errors = Vec::new();
+        let lines = collapse(lines);
+        let fenced = markdown::fenced_lines(&lines);
+        for (index, line) in lines.iter().enumerate() {
+            expanded.push(line.clone());
+            let Some((path, fragment)) = directive(line).filter(|_| !fenced[index]) else {
+                continue;
+            };
+            match self.read(&path, fragment.as_deref()) {
+                Ok(included) => {
+                    let extension = Path::new(&path)
+                        .extension()
+                        .map(|extension| extension.to_string_lossy().to_lowercase())
+                        .unwrap_or_default();
+                    if TEXT_EXTENSIONS.contains(&extension.as_str()) {
+                        if let Err(message) = check_text(&path, &included) {
+                            errors.push(IncludeError {
+                                line: index,
+                                message,
+                            });
+                            continue;
+                        }
+                        expanded.extend(included);
+                    } else {
+                        let language = LANGUAGES
+                            .iter()
+                            .find(|(known, _)| *known == extension)
+                            .map_or("", |(_, language)| language);
+                        let fence = fence(&included);
+                        expanded.push(format!("{}{}", fence, language));
+                        expanded.extend(included);
+                        expanded.push(fence);
+                    }
+                    expanded.push(END_MARKER.to_string());
+                }
+                Err(message) => errors.push(IncludeError {
+                    line: index,
+                    message,
+                }),
+            }
+        }
+        (expanded, errors)
+    }
+
+    pub fn expand_sections(
+        &self,
+        sections: &HashMap<ContentListItem, Vec<String>>,
+    ) -> (HashMap<ContentListItem, Vec<String>>, SectionErrors) {
+        let mut expanded = HashMap::new();
+        let mut errors = Vec::new();
+        for (section, lines) in sections {
+            let (lines, section_errors) = self.expand(lines);
+            expanded.insert(section.clone(), lines);
+            errors.extend(
+                section_errors
+                    .into_iter()
+                    .map(|error| (section.clone(), error)),
+            );
+        }
+        errors.sort_by_key(|(section, error)| (Content::index_for_section(section), error.line));
+        (expanded, errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+    use std::process;
+
+    #[test]
+    fn test_includes_are_expanded_and_collapsed() {
+        let dir = std::env::temp_dir().join(format!("txtui-includes-{}", process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(
+            dir.join("src/main.rs"),
+            "use std::io;\n// ANCHOR: main\nfn main() {\n    // ANCHOR: body\n    run();\n    // ANCHOR_END: body\n}\n// ANCHOR_END: main\n",
+        )
+        .unwrap();
+        fs::write(dir.join("notes.md"), "Shared *note*\n").unwrap();
+        let includes = Includes {
+            base_dir: dir.clone(),
+        };
+
+        let source = lines(&[
+            "Intro",
+            "<!-- include: src/main.rs#main -->",
+            "<!-- include: src/main.rs#L1 -->",
+            "<!-- include: notes.md -->",
+            "<!-- include: src/main.rs#setup -->",
+            "<!-- include: src/main.rs#L5-L20 -->",
+        ]);
+        let (expanded, errors) = includes.expand(&source);
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(
+            expanded,
+            lines(&[
+                "Intro",
+                "<!-- include: src/main.rs#main -->",
+                "```rust",
+                "fn main() {",
+                "    run();",
+                "}",
+                "```",
+                END_MARKER,
+                "<!-- include: src/main.rs#L1 -->",
+                "```rust",
+                "use std::io;",
+                "```",
+                END_MARKER,
+                "<!-- include: notes.md -->",
+                "Shared *note*",
+                END_MARKER,
+                "<!-- include: src/main.rs#setup -->",
+                "<!-- include: src/main.rs#L5-L20 -->",
+            ])
+        );
+        let messages: Vec<(usize, &str)> = errors
+            .iter()
+            .map(|error| (error.line, error.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                (4, "src/main.rs#setup: region 'setup' no longer exists"),
+                (5, "src/main.rs#L5-L20: file has only 8 line(s)"),
+            ]
+        );
+        assert_eq!(collapse(&expanded), source);
+    }
+
+    #[test]
+    fn test_unsafe_includes_are_refused() {
+        let root = std::env::temp_dir().join(format!("txtui-includes-unsafe-{}", process::id()));
+        let dir = root.join("docs");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(root.join("secret.txt"), "secret\n").unwrap();
+        fs::write(dir.join("ticks.sh"), "echo ```` done\n").unwrap();
+        fs::write(dir.join("nested.md"), "<!-- include: other.md -->\n").unwrap();
+        fs::write(dir.join("open.md"), "```sh\necho\n").unwrap();
+        fs::write(
+            dir.join("heading.md"),
+            "## Heading\n```md\n<!-- /include -->\n```\n",
+        )
+        .unwrap();
+        let includes = Includes {
+            base_dir: dir.clone(),
+        };
+
+        let source = lines(&[
+            "<!-- include: ../secret.txt -->",
+            &format!("<!-- include: {} -->", root.join("secret.txt").display()),
+            "<!-- include: nested.md -->",
+            "<!-- include: open.md -->",
+            "```md",
+            "<!-- include: ticks.sh -->",
+            "```",
+            "<!-- include: ticks.sh -->",
+            "<!-- include: heading.md -->",
+        ]);
+        let (expanded, errors) = includes.expand(&source);
+        let _ = fs::remove_dir_all(&root);
+        let lines_with_errors: Vec<usize> = errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines_with_errors, vec![0, 1, 2, 3]);
+        assert!(errors[0].message.contains("included files must be inside"));
+        assert!(errors[1].message.contains("included files must be inside"));
+        assert!(errors[2].message.ends_with("outside a code block"));
+        assert!(errors[3].message.ends_with("leaves a code fence open"));
+        assert_eq!(
+            expanded[4..],
+            lines(&[
+                "```md",
+                "<!-- include: ticks.sh -->",
+                "```",
+                "<!-- include: ticks.sh -->",
+                "`````sh",
+                "echo ```` done",
+                "`````",
+                END_MARKER,
+                "<!-- include: heading.md -->",
+                "## Heading",
+                "```md",
+                END_MARKER,
+                "```",
+                END_MARKER,
+            ])
+        );
+        assert_eq!(collapse(&expanded), source);
+    }
+}
@@ -0,0 +1,93 @@
+use color_eyre::eyre::{Result, WrapErr};
+use std::{fs, path::Path};
+
+use crate::config::PROJECT_CONFIG_FILE;
+use crate::packs::Pack;
+use crate::sections::SectionDefinitions;
+
+pub const TEMPLATE_DIR: &str = "templates";
+pub const TEMPLATES: [(&str, &str); 5] = [
+    ("name.md", include_str!("../templates/name.md")),
+    ("tutorials.md", include_str!("../templates/tutorials.md")),
+    ("guides.md", include_str!("../templates/guides.md")),
+    (
+        "explanation.md",
+        include_str!("../templates/explanation.md"),
+    ),
+    ("reference.md", include_str!("../templates/reference.md")),
+];
+const PROJECT_CONFIG: &str = r#"# txtui project configuration - values here override ~/.config/txtui/config.toml
+
+[markdown]
+format_on_save = false
+list_marker = "-"
+
+[spell]
+enabled = false
+language = "en_US"
+
+[stats.targets.tutorials]
+min_words = 1
+
+[stats.targets.reference]
+min_words = 100
+"#;
+
+pub fn write_file(path: &Path, contents: &str, force: bool) -> Result<bool> {
+    if path.exists() && !force {
+        return Ok(false);
+    }
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .wrap_err_with(|| format!("Unable to create {}", parent.display()))?;
+    }
+    fs::write(path, contents).wrap_err_with(|| format!("Unable to write {}", path.display()))?;
+    Ok(true)
+}
+
+fn report(path: &Path, written: bool) {
+    if written {
+        println!("created {}", path.display());
+    } else {
+        println!("skipped {} (already exists)", path.display());
+    }
+}
+
+pub fn run(dir: &Path, force: bool, pack: &Pack) -> Result<()> {
+    let mut definitions = SectionDefinitions::default();
+    definitions.template_dir = dir.join(TEMPLATE_DIR);
+    for (path, written) in pack.write_templates(&definitions, force)? {
+        report(&path, written);
+    }
+    let config = dir.join(PROJECT_CONFIG_FILE);
+    let written = write_file(&config, PROJECT_CONFIG, force)?;
+    report(&config, written);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::read_config_file;
+    use std::{env, process};
+
+    #[test]
+    fn test_init_scaffolds_templates_and_config() {
+        let dir = env::temp_dir().join(format!("txtui-init-{}", process::id()));
+        run(&dir, false, &Pack::default_pack()).unwrap();
+        assert!(dir.join("templates/tutorials.md").is_file());
+        assert!(read_config_file(&dir.join(PROJECT_CONFIG_FILE)).is_ok());
+
+        fs::write(dir.join("templates/name.md"), "kept").unwrap();
+        run(&dir, false, &Pack::default_pack()).unwrap();
+        let kept = fs::read_to_string(dir.join("templates/name.md")).unwrap();
+        run(&dir, true, &Pack::default_pack()).unwrap();
+        let replaced = fs::read_to_string(dir.join("templates/name.md")).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(kept, "kept");
+        assert_eq!(replaced, TEMPLATES[0].1);
+    }
+}
@@ -0,0 +1,545 @@
+use color_eyre::eyre::{eyre, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::{collections::HashMap, fmt};
+
+use crate::config::LoadedConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Save,
+    EditSection,
+    ExitPrompt,
+    NextSection,
+    PreviousSection,
+    ExpandSection,
+    CollapseSection,
+    MoveSectionUp,
+    MoveSectionDown,
+    RenameSection,
+    Find,
+    FindAll,
+    Replace,
+    Undo,
+    OpenInEditor,
+    ToggleDiagnostics,
+    ToggleOutline,
+    ToggleStats,
+    ToggleSpellCheck,
+    EditFrontMatter,
+    SpellSuggestions,
+    InsertSnippet,
+    NextTabStop,
+    PreviousTabStop,
+    ExitInsert,
+    Paste,
+    NewLine,
+    DeleteBackward,
+    CursorLeft,
+    CursorRight,
+    CursorUp,
+    CursorDown,
+    ScrollTop,
+    ScrollBottom,
+    NextButton,
+    PreviousButton,
+    ConfirmButton,
+    CancelPopup,
+}
+
+impl Action {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Save => "save",
+            Action::EditSection => "edit_section",
+            Action::ExitPrompt => "exit_prompt",
+            Action::NextSection => "next_section",
+            Action::PreviousSection => "previous_section",
+            Action::ExpandSection => "expand_section",
+            Action::CollapseSection => "collapse_section",
+            Action::MoveSectionUp => "move_section_up",
+            Action::MoveSectionDown => "move_section_down",
+            Action::RenameSection => "rename_section",
+            Action::Find => "find",
+            Action::FindAll => "find_all",
+            Action::Replace => "replace",
+            Action::Undo => "undo",
+            Action::OpenInEditor => "open_in_editor",
+            Action::ToggleDiagnostics => "toggle_diagnostics",
+            Action::ToggleOutline => "toggle_outline",
+            Action::ToggleStats => "toggle_stats",
+            Action::ToggleSpellCheck => "toggle_spell_check",
+            Action::EditFrontMatter => "edit_front_matter",
+            Action::SpellSuggestions => "spell_suggestions",
+            Action::InsertSnippet => "insert_snippet",
+            Action::NextTabStop => "next_tab_stop",
+            Action::PreviousTabStop => "previous_tab_stop",
+            Action::ExitInsert => "exit_insert",
+            Action::Paste => "paste",
+            Action::NewLine => "new_line",
+            Action::DeleteBackward => "delete_backward",
+            Action::CursorLeft => "cursor_left",
+            Action::CursorRight => "cursor_right",
+            Action::CursorUp => "cursor_up",
+            Action::CursorDown => "cursor_down",
+            Action::ScrollTop => "scroll_top",
+            Action::ScrollBottom => "scroll_bottom",
+            Action::NextButton => "next_button",
+            Action::PreviousButton => "previous_button",
+            Action::ConfirmButton => "confirm_button",
+            Action::CancelPopup => "cancel_popup",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyContext {
+    Navigation,
+    Editor,
+    Popup,
+}
+
+impl KeyContext {
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyContext::Navigation => "navigation",
+            KeyContext::Editor => "editor",
+            KeyContext::Popup => "popup",
+        }
+    }
+
+    fn hint_groups(&self) -> Vec<(Vec<Action>, &'static str)> {
+        match self {
+            KeyContext::Navigation => vec![
+                (
+                    vec![Action::PreviousSection, Action::NextSection],
+                    "navigate",
+                ),
+                (vec![Action::CollapseSection, Action::ExpandSection], "fold"),
+                (
+                    vec![Action::MoveSectionUp, Action::MoveSectionDown],
+                    "reorder",
+                ),
+                (vec![Action::RenameSection], "rename"),
+                (vec![Action::EditSection], "edit"),
+                (vec![Action::Save], "save"),
+                (vec![Action::Find], "find"),
+                (vec![Action::FindAll], "search all"),
+                (vec![Action::OpenInEditor], "$EDITOR"),
+                (vec![Action::ToggleDiagnostics], "lint"),
+                (vec![Action::ToggleOutline], "outline"),
+                (vec![Action::ToggleStats], "stats"),
+                (vec![Action::EditFrontMatter], "metadata"),
+                (vec![Action::ExitPrompt], "exit"),
+            ],
+            KeyContext::Editor => vec![
+                (
+                    vec![
+                        Action::CursorLeft,
+                        Action::CursorUp,
+                        Action::CursorDown,
+                        Action::CursorRight,
+                    ],
+                    "navigate",
+                ),
+                (vec![Action::ScrollTop], "page up"),
+                (vec![Action::ScrollBottom], "page down"),
+                (vec![Action::Find], "find"),
+                (vec![Action::Replace], "replace"),
+                (vec![Action::Undo], "undo"),
+                (vec![Action::SpellSuggestions], "spelling"),
+                (vec![Action::InsertSnippet], "snippet"),
+                (vec![Action::ExitInsert], "exit"),
+            ],
+            KeyContext::Popup => vec![
+                (vec![Action::PreviousButton, Action::NextButton], "choose"),
+                (vec![Action::ConfirmButton], "confirm"),
+                (vec![Action::CancelPopup], "cancel"),
+            ],
+        }
+    }
+
+    fn defaults(&self) -> Vec<(Action, Vec<&'static str>)> {
+        match self {
+            KeyContext::Navigation => vec![
+                (Action::Quit, vec!["q"]),
+                (Action::Save, vec!["s"]),
+                (Action::EditSection, vec!["i"]),
+                (Action::ExitPrompt, vec!["esc"]),
+                (Action::NextSection, vec!["down"]),
+                (Action::PreviousSection, vec!["up"]),
+                (Action::ExpandSection, vec!["right"]),
+                (Action::CollapseSection, vec!["left"]),
+                (Action::MoveSectionUp, vec!["shift+up"]),
+                (Action::MoveSectionDown, vec!["shift+down"]),
+                (Action::RenameSection, vec!["r"]),
+                (Action::Find, vec!["/", "ctrl+f"]),
+                (Action::FindAll, vec!["ctrl+g"]),
+                (Action::Replace, vec!["ctrl+r"]),
+                (Action::Undo, vec!["u"]),
+                (Action::OpenInEditor, vec!["e"]),
+                (Action::ToggleDiagnostics, vec!["d"]),
+                (Action::ToggleOutline, vec!["o"]),
+                (Action::ToggleStats, vec!["t"]),
+                (Action::ToggleSpellCheck, vec!["w"]),
+                (Action::EditFrontMatter, vec!["m"]),
+            ],
+            KeyContext::Editor => vec![
+                (Action::ExitInsert, vec!["esc"]),
+                (Action::Paste, vec!["ctrl+v"]),
+                (Action::NewLine, vec!["enter"]),
+                (Action::DeleteBackward, vec!["backspace"]),
+                (Action::CursorLeft, vec!["left"]),
+                (Action::CursorRight, vec!["right"]),
+                (Action::CursorUp, vec!["up"]),
+                (Action::CursorDown, vec!["down"]),
+                (Action::ScrollTop, vec!["f1"]),
+                (Action::ScrollBottom, vec!["f2"]),
+                (Action::Find, vec!["ctrl+f"]),
+                (Action::FindAll, vec!["ctrl+g"]),
+                (Action::Replace, vec!["ctrl+r"]),
+                (Action::Undo, vec!["ctrl+z"]),
+                (Action::OpenInEditor, vec!["ctrl+e"]),
+                (Action::ToggleDiagnostics, vec!["ctrl+d"]),
+                (Action::ToggleOutline, vec!["ctrl+o"]),
+                (Action::ToggleStats, vec!["ctrl+t"]),
+                (Action::ToggleSpellCheck, vec!["ctrl+w"]),
+                (Action::SpellSuggestions, vec!["ctrl+k"]),
+                (Action::InsertSnippet, vec!["ctrl+n"]),
+                (Action::NextTabStop, vec!["tab"]),
+                (Action::PreviousTabStop, vec!["backtab"]),
+            ],
+            KeyContext::Popup => vec![
+                (Action::CancelPopup, vec!["esc"]),
+                (Action::NextButton, vec!["right"]),
+                (Action::PreviousButton, vec!["left"]),
+                (Action::ConfirmButton, vec!["enter"]),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = if let KeyCode::Char(_) = code {
+            modifiers - KeyModifiers::SHIFT
+        } else {
+            modifiers
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn parse(binding: &str) -> Result<Self> {
+        let (modifier_names, key_name) = match binding.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None => binding.rsplit_once('+').unwrap_or(("", binding)),
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(eyre!("unknown modifier '{}' in '{}'", modifier, binding)),
+            };
+        }
+        let code = match key_name.to_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "backspace" => KeyCode::Backspace,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "delete" | "del" => KeyCode::Delete,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "space" => KeyCode::Char(' '),
+            name => {
+                let mut chars = key_name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(number) if (1..=12).contains(&number) => KeyCode::F(number),
+                        _ => return Err(eyre!("unknown key '{}' in '{}'", key_name, binding)),
+                    },
+                }
+            }
+        };
+        Ok(KeyBinding::new(code, modifiers))
+    }
+
+    pub fn from_event(key: KeyEvent) -> Self {
+        KeyBinding::new(key.code, key.modifiers)
+    }
+
+    fn is_plain_char(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_))
+            && !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Up => write!(f, "▲"),
+            KeyCode::Down => write!(f, "▼"),
+            KeyCode::Left => write!(f, "◄"),
+            KeyCode::Right => write!(f, "►"),
+            KeyCode::Esc => write!(f, "ESC"),
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::CONTROL) => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(number) => write!(f, "F{}", number),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<KeyContext, Vec<(KeyBinding, Action)>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::from_configs(&[]).expect("default keymap is valid")
+    }
+}
+
+impl Keymap {
+    pub fn from_configs(configs: &[LoadedConfig]) -> Result<Self> {
+        let mut bindings = HashMap::new();
+        for context in [
+            KeyContext::Navigation,
+            KeyContext::Editor,
+            KeyContext::Popup,
+        ] {
+            let mut actions: Vec<(Action, Vec<String>)> = context
+                .defaults()
+                .into_iter()
+                .map(|(action, keys)| (action, keys.into_iter().map(String::from).collect()))
+                .collect();
+            for config in configs {
+                for (action, keys) in config.file.keys.bindings(context) {
+                    match actions.iter_mut().find(|(existing, _)| existing == action) {
+                        Some((_, existing_keys)) => *existing_keys = keys.keys(),
+                        None => {
+                            return Err(eyre!(
+                                "{}: action '{}' is not available in [keys.{}]",
+                                config.path.display(),
+                                action.name(),
+                                context.name()
+                            ))
+                        }
+                    }
+                }
+            }
+            bindings.insert(context, Keymap::validate(context, &actions)?);
+        }
+        Ok(Self { bindings })
+    }
+
+    fn validate(
+        context: KeyContext,
+        actions: &[(Action, Vec<String>)],
+    ) -> Result<Vec<(KeyBinding, Action)>> {
+        let mut resolved: Vec<(KeyBinding, Action, &str)> = Vec::new();
+        for (action, keys) in actions {
+            for key in keys {
+                let binding = KeyBinding::parse(key).map_err(|error| {
+                    eyre!(
+                        "invalid binding for '{}' in [keys.{}]: {}",
+                        action.name(),
+                        context.name(),
+                        error
+                    )
+                })?;
+                if context == KeyContext::Editor && binding.is_plain_char() {
+                    return Err(eyre!(
+                        "'{}' cannot be bound to '{}' in [keys.editor]: plain characters are reserved for typing",
+                        key,
+                        action.name()
+                    ));
+                }
+                if let Some((_, other, other_key)) = resolved
+                    .iter()
+                    .find(|(existing, other, _)| *existing == binding && other != action)
+                {
+                    return Err(eyre!(
+                        "conflicting bindings in [keys.{}]: '{}' ({}) and '{}' ({}) are the same key",
+                        context.name(),
+                        other_key,
+                        other.name(),
+                        key,
+                        action.name()
+                    ));
+                }
+                resolved.push((binding, *action, key));
+            }
+        }
+        Ok(resolved
+            .into_iter()
+            .map(|(binding, action, _)| (binding, action))
+            .collect())
+    }
+
+    pub fn action(&self, context: KeyContext, key: KeyEvent) -> Option<Action> {
+        let pressed = KeyBinding::from_event(key);
+        self.bindings
+            .get(&context)?
+            .iter()
+            .find(|(binding, _)| *binding == pressed)
+            .map(|(_, action)| *action)
+    }
+
+    pub fn keys_for(&self, context: KeyContext, action: Action) -> Vec<KeyBinding> {
+        self.bindings
+            .get(&context)
+            .map(|bindings| {
+                bindings
+                    .iter()
+                    .filter(|(_, bound)| *bound == action)
+                    .map(|(binding, _)| *binding)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn hint(&self, context: KeyContext) -> String {
+        context
+            .hint_groups()
+            .into_iter()
+            .filter_map(|(actions, label)| {
+                let keys: Vec<String> = actions
+                    .iter()
+                    .filter_map(|action| self.keys_for(context, *action).first().copied())
+                    .map(|binding| binding.to_string())
+                    .collect();
+                (!keys.is_empty()).then(|| format!("{} - {}", keys.join(" "), label))
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFile;
+    use std::path::PathBuf;
+
+    fn config(toml: &str) -> LoadedConfig {
+        LoadedConfig {
+            path: PathBuf::from(".txtui.toml"),
+            file: toml::from_str::<ConfigFile>(toml).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_parse_bindings() {
+        assert_eq!(
+            KeyBinding::parse("ctrl+f").unwrap(),
+            KeyBinding::new(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            KeyBinding::parse("F2").unwrap(),
+            KeyBinding::new(KeyCode::F(2), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            KeyBinding::parse("ctrl++").unwrap(),
+            KeyBinding::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert!(KeyBinding::parse("hyper+x").is_err());
+        assert!(KeyBinding::parse("f13").is_err());
+    }
+
+    #[test]
+    fn test_default_keymap() {
+        let keymap = Keymap::default();
+        let key = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE);
+        assert_eq!(
+            keymap.action(KeyContext::Navigation, key),
+            Some(Action::Save)
+        );
+        assert_eq!(keymap.action(KeyContext::Editor, key), None);
+        assert_eq!(
+            keymap.hint(KeyContext::Navigation),
+            "▲ ▼ - navigate, ◄ ► - fold, Shift+▲ Shift+▼ - reorder, r - rename, i - edit, s - save, / - find, Ctrl+G - search all, e - $EDITOR, d - lint, o - outline, t - stats, m - metadata, ESC - exit"
+        );
+    }
+
+    #[test]
+    fn test_config_overrides_default_binding() {
+        let keymap = Keymap::from_configs(&[config(
+            "[keys.editor]\nscroll_top = \"pageup\"\nscroll_bottom = [\"pagedown\", \"ctrl+end\"]",
+        )])
+        .unwrap();
+        let page_up = KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE);
+        let f1 = KeyEvent::new(KeyCode::F(1), KeyModifiers::NONE);
+        assert_eq!(
+            keymap.action(KeyContext::Editor, page_up),
+            Some(Action::ScrollTop)
+        );
+        assert_eq!(keymap.action(KeyContext::Editor, f1), None);
+        assert_eq!(
+            keymap
+                .keys_for(KeyContext::Editor, Action::ScrollBottom)
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_conflicting_bindings_are_rejected() {
+        let error = Keymap::from_configs(&[config("[keys.navigation]\nsave = \"q\"")])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("conflicting bindings in [keys.navigation]"));
+        assert!(error.contains("quit"));
+    }
+
+    #[test]
+    fn test_plain_characters_are_rejected_in_editor() {
+        assert!(Keymap::from_configs(&[config("[keys.editor]\nundo = \"u\"")]).is_err());
+    }
+
+    #[test]
+    fn test_action_must_belong_to_context() {
+        let error = Keymap::from_configs(&[config("[keys.popup]\nsave = \"s\"")])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("'save' is not available in [keys.popup]"));
+    }
+
+    #[test]
+    fn test_unknown_action_is_rejected() {
+        assert!(toml::from_str::<ConfigFile>("[keys.navigation]\nfly = \"f\"").is_err());
+    }
+}
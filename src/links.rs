@@ -0,0 +1,268 @@
+use regex::Regex;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use crate::content::{Content, ContentListItem};
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::document::{self, RenderOptions};
+use crate::lint::prose_lines;
+
+static HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^ {0,3}#{1,6}\s+(.*?)\s*#*\s*$").expect("valid regex"));
+static LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(!?)\[[^\]]*\]\(\s*<?([^)\s>]*)>?(?:\s+"[^"]*")?\s*\)"#).expect("valid regex")
+});
+static INLINE_LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"!?\[([^\]]*)\]\([^)]*\)").expect("valid regex"));
+static CODE_SPAN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`[^`]*`").expect("valid regex"));
+static URL_SCHEME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z][A-Za-z0-9+.-]*:").expect("valid regex"));
+
+pub fn heading_text(heading: &str) -> String {
+    INLINE_LINK.replace_all(heading, "$1").trim().to_string()
+}
+
+pub fn slugify(heading: &str) -> String {
+    heading_text(heading)
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn headings(lines: &[String]) -> Vec<(usize, usize, String)> {
+    prose_lines(lines)
+        .into_iter()
+        .filter_map(|(index, line)| {
+            let captures = HEADING.captures(line)?;
+            let level = line.trim_start().chars().take_while(|c| *c == '#').count();
+            Some((index, level, captures[1].to_string()))
+        })
+        .collect()
+}
+
+pub fn anchors(lines: &[String]) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    headings(lines)
+        .into_iter()
+        .map(|(_, _, title)| {
+            let slug = slugify(&title);
+            let count = seen.entry(slug.clone()).or_insert(0);
+            let anchor = if *count == 0 {
+                slug
+            } else {
+                format!("{}-{}", slug, count)
+            };
+            *count += 1;
+            anchor
+        })
+        .collect()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = text
+            .get(index + 1..index + 3)
+            .filter(|_| bytes[index] == b'%')
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+pub struct LinkChecker {
+    anchors: HashSet<String>,
+    base_dir: PathBuf,
+}
+
+impl LinkChecker {
+    pub fn new(document_lines: &[String], base_dir: &Path) -> Self {
+        Self {
+            anchors: anchors(document_lines).into_iter().collect(),
+            base_dir: base_dir.to_path_buf(),
+        }
+    }
+
+    pub fn check_section(&self, section: &ContentListItem, lines: &[String]) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for (index, line) in prose_lines(lines) {
+            let line = CODE_SPAN.replace_all(line, "");
+            for captures in LINK.captures_iter(&line) {
+                let kind = if captures[1].is_empty() {
+                    "link"
+                } else {
+                    "image"
+                };
+                if let Some((rule, message)) = self.check_target(kind, &captures[2]) {
+                    diagnostics.push(Diagnostic {
+                        section: section.clone(),
+                        line: Some(index),
+                        severity: Severity::Error,
+                        rule,
+                        message,
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+
+    fn check_target(&self, kind: &str, target: &str) -> Option<(&'static str, String)> {
+        if target.is_empty() || target.starts_with("//") || URL_SCHEME.is_match(target) {
+            return None;
+        }
+        let (path, fragment) = match target.split_once('#') {
+            Some((path, fragment)) => (path, Some(percent_decode(fragment))),
+            None => (target, None),
+        };
+        let path = percent_decode(path.split('?').next().unwrap_or_default());
+        if path.is_empty() {
+            let fragment = fragment.unwrap_or_default();
+            return (!self.anchors.contains(&fragment)).then(|| {
+                (
+                    "broken-anchor",
+                    format!("No heading in this document has the anchor '#{}'", fragment),
+                )
+            });
+        }
+
+        let file_path = self.base_dir.join(path.trim_start_matches('/'));
+        if !file_path.exists() {
+            return Some((
+                "broken-link",
+                format!(
+                    "The {} target '{}' does not exist",
+                    kind,
+                    file_path.display()
+                ),
+            ));
+        }
+        let fragment = fragment.filter(|fragment| !fragment.is_empty())?;
+        if file_path
+            .extension()
+            .is_some_and(|extension| extension == "md")
+        {
+            let lines: Vec<String> = fs::read_to_string(&file_path)
+                .ok()?
+                .lines()
+                .map(|line| line.to_string())
+                .collect();
+            if !anchors(&lines).contains(&fragment) {
+                return Some((
+                    "broken-anchor",
+                    format!(
+                        "No heading in '{}' has the anchor '#{}'",
+                        file_path.display(),
+                        fragment
+                    ),
+                ));
+            }
+        }
+        None
+    }
+
+    pub fn check_document(&self, sections: &[(ContentListItem, Vec<String>)]) -> Vec<Diagnostic> {
+        sections
+            .iter()
+            .flat_map(|(section, lines)| self.check_section(section, lines))
+            .collect()
+    }
+}
+
+pub fn check_content(content: &Content, base_dir: &Path) -> Vec<Diagnostic> {
+    let sections = content.edited_sections();
+    LinkChecker::new(
+        &document::render_sections(
+            &sections,
+            &RenderOptions {
+                sections: content.definitions().clone(),
+                ..RenderOptions::default()
+            },
+        ),
+        base_dir,
+    )
+    .check_document(&sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+
+    #[test]
+    fn test_slugify_matches_github() {
+        assert_eq!(slugify("📝 TXTUI"), "-txtui");
+        assert_eq!(
+            slugify("Tutorial 1: Running project locally"),
+            "tutorial-1-running-project-locally"
+        );
+        assert_eq!(slugify("**[Setup](#setup)** `cargo`"), "setup-cargo");
+        assert_eq!(slugify("Diátaxis & you"), "diátaxis--you");
+    }
+
+    #[test]
+    fn test_duplicate_headings_get_suffixes() {
+        let document = lines(&[
+            "## Objective",
+            "```",
+            "# not a heading",
+            "```",
+            "### Objective",
+        ]);
+        assert_eq!(anchors(&document), vec!["objective", "objective-1"]);
+    }
+
+    #[test]
+    fn test_broken_anchors_and_paths_are_reported() {
+        let dir = std::env::temp_dir().join(format!("txtui-links-{}", std::process::id()));
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::write(dir.join("docs/setup guide.md"), "# Setup\n## Über uns\n").unwrap();
+        fs::write(dir.join("logo.png"), "").unwrap();
+        let document = lines(&["# txtui", "## Tutorials", "### Getting started"]);
+        let checker = LinkChecker::new(&document, &dir);
+        let diagnostics = checker.check_section(
+            &ContentListItem::Tutorials,
+            &lines(&[
+                "[ok](#getting-started) [ok](https://example.com) [ok](/logo.png)",
+                "[broken](#getting-startd) `[ignored](#nope)`",
+                "![image](missing.png) ![image](/missing.png)",
+                "[ok](docs/setup%20guide.md#%C3%BCber-uns) [missing](docs/setup%20guide.md#nope)",
+            ]),
+        );
+        let _ = fs::remove_dir_all(&dir);
+        let found: Vec<(Option<usize>, &str)> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.rule))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (Some(1), "broken-anchor"),
+                (Some(2), "broken-link"),
+                (Some(2), "broken-link"),
+                (Some(3), "broken-anchor"),
+            ]
+        );
+    }
+}
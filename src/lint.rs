@@ -0,0 +1,413 @@
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use regex::Regex;
+use std::{collections::HashSet, fs, path::Path, sync::LazyLock};
+
+use crate::config::LoadedConfig;
+use crate::content::{Content, ContentListItem};
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::document;
+use crate::links::LinkChecker;
+use crate::markdown::{self, MarkdownStyle};
+use crate::placeholders::TemplateFingerprints;
+use crate::sections::SectionDefinitions;
+use crate::stats::StatsTargets;
+
+static ORDERED_ITEM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*\d+[.)]\s+\S").expect("valid regex"));
+static LINK_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*\d+[.)]\s+(\*\*)?\[[^\]]*\]\([^)]*\)(\*\*)?\s*$").expect("valid regex")
+});
+static STEP_MENTION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bstep \d+\b").expect("valid regex"));
+static OBJECTIVE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(#+\s*)?(>\s*)?(objective|what you('ll| will) (learn|build)|by the end of this tutorial|in this tutorial)")
+        .expect("valid regex")
+});
+static GOAL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(#+\s*(\S+\s+\d+:\s*)?(goal|how to\b))|^(goal:|this guide shows how to)")
+        .expect("valid regex")
+});
+static SECOND_PERSON: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(you|your|yours|you're|you'll|let's|we'll)\b").expect("valid regex")
+});
+static NARRATIVE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*([-*]\s+)?(first|next|then|finally|now|afterwards),?\s")
+        .expect("valid regex")
+});
+
+pub const CHECK_RULES: [&str; 5] = [
+    "unedited-section",
+    "template-text",
+    "broken-anchor",
+    "broken-link",
+    "word-count",
+];
+
+type Findings = Vec<(Option<usize>, String)>;
+
+struct Rule {
+    id: &'static str,
+    section: ContentListItem,
+    severity: Severity,
+    check: fn(&[(usize, &str)]) -> Findings,
+}
+
+const RULES: [Rule; 7] = [
+    Rule {
+        id: "project-name",
+        section: ContentListItem::ProjectName,
+        severity: Severity::Error,
+        check: check_project_name,
+    },
+    Rule {
+        id: "tutorial-numbered-steps",
+        section: ContentListItem::Tutorials,
+        severity: Severity::Error,
+        check: check_numbered_steps,
+    },
+    Rule {
+        id: "tutorial-objective",
+        section: ContentListItem::Tutorials,
+        severity: Severity::Warning,
+        check: check_objective,
+    },
+    Rule {
+        id: "guide-goal",
+        section: ContentListItem::Guides,
+        severity: Severity::Warning,
+        check: check_goal,
+    },
+    Rule {
+        id: "reference-second-person",
+        section: ContentListItem::Reference,
+        severity: Severity::Warning,
+        check: check_second_person,
+    },
+    Rule {
+        id: "reference-narrative",
+        section: ContentListItem::Reference,
+        severity: Severity::Warning,
+        check: check_narrative,
+    },
+    Rule {
+        id: "explanation-step-list",
+        section: ContentListItem::Explanation,
+        severity: Severity::Warning,
+        check: check_step_list,
+    },
+];
+
+pub fn prose_lines(lines: &[String]) -> Vec<(usize, &str)> {
+    lines
+        .iter()
+        .zip(markdown::fenced_lines(lines))
+        .enumerate()
+        .filter(|(_, (_, fenced))| !fenced)
+        .map(|(index, (line, _))| (index, line.as_str()))
+        .collect()
+}
+
+fn is_step(line: &str) -> bool {
+    ORDERED_ITEM.is_match(line) && !LINK_ITEM.is_match(line)
+}
+
+fn check_project_name(lines: &[(usize, &str)]) -> Findings {
+    match lines.first() {
+        Some((_, name)) if !name.trim().is_empty() => Vec::new(),
+        _ => vec![(None, "Project name is empty".to_string())],
+    }
+}
+
+fn check_numbered_steps(lines: &[(usize, &str)]) -> Findings {
+    if lines.iter().any(|(_, line)| is_step(line)) {
+        Vec::new()
+    } else {
+        vec![(
+            None,
+            "Tutorials should walk the reader through numbered steps (`1. ...`)".to_string(),
+        )]
+    }
+}
+
+fn check_objective(lines: &[(usize, &str)]) -> Findings {
+    if lines
+        .iter()
+        .any(|(_, line)| OBJECTIVE.is_match(line.trim()))
+    {
+        Vec::new()
+    } else {
+        vec![(
+            None,
+            "Tutorials should state an objective, e.g. under an 'Objective' heading".to_string(),
+        )]
+    }
+}
+
+fn check_goal(lines: &[(usize, &str)]) -> Findings {
+    if lines.iter().any(|(_, line)| GOAL.is_match(line.trim())) {
+        Vec::new()
+    } else {
+        vec![(
+            None,
+            "How-to guides should start from a goal: add a 'Goal' heading or title guides 'How to ...'"
+                .to_string(),
+        )]
+    }
+}
+
+fn check_second_person(lines: &[(usize, &str)]) -> Findings {
+    lines
+        .iter()
+        .filter_map(|(index, line)| {
+            let found = SECOND_PERSON.find(line)?;
+            Some((
+                Some(*index),
+                format!(
+                    "Reference should describe rather than instruct; avoid '{}'",
+                    found.as_str()
+                ),
+            ))
+        })
+        .collect()
+}
+
+fn check_narrative(lines: &[(usize, &str)]) -> Findings {
+    lines
+        .iter()
+        .filter(|(_, line)| NARRATIVE.is_match(line))
+        .map(|(index, _)| {
+            (
+                Some(*index),
+                "Reference should not narrate a sequence of actions".to_string(),
+            )
+        })
+        .collect()
+}
+
+fn check_step_list(lines: &[(usize, &str)]) -> Findings {
+    lines
+        .iter()
+        .filter(|(_, line)| is_step(line) || STEP_MENTION.is_match(line))
+        .map(|(index, _)| {
+            (
+                Some(*index),
+                "Explanation should discuss concepts; move step lists to a tutorial or how-to guide"
+                    .to_string(),
+            )
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintRules {
+    pub deny_warnings: bool,
+    disabled: HashSet<String>,
+}
+
+impl LintRules {
+    pub fn rule_names() -> Vec<&'static str> {
+        RULES
+            .iter()
+            .map(|rule| rule.id)
+            .chain(CHECK_RULES)
+            .collect()
+    }
+
+    pub fn from_configs(configs: &[LoadedConfig]) -> Result<Self> {
+        let mut rules = LintRules::default();
+        for config in configs {
+            let lint = &config.file.lint;
+            if let Some(deny_warnings) = lint.deny_warnings {
+                rules.deny_warnings = deny_warnings;
+            }
+            for (rule, enabled) in &lint.rules {
+                if !LintRules::rule_names().contains(&rule.as_str()) {
+                    return Err(eyre!(
+                        "{}: unknown lint rule '{}' (available: {})",
+                        config.path.display(),
+                        rule,
+                        LintRules::rule_names().join(", ")
+                    ));
+                }
+                if *enabled {
+                    rules.disabled.remove(rule);
+                } else {
+                    rules.disabled.insert(rule.clone());
+                }
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn apply(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+        diagnostics
+            .into_iter()
+            .filter(|diagnostic| !self.disabled.contains(diagnostic.rule))
+            .collect()
+    }
+}
+
+pub fn lint_section(section: &ContentListItem, lines: &[String]) -> Vec<Diagnostic> {
+    let prose = prose_lines(lines);
+    RULES
+        .iter()
+        .filter(|rule| &rule.section == section)
+        .flat_map(|rule| {
+            (rule.check)(&prose)
+                .into_iter()
+                .map(|(line, message)| Diagnostic {
+                    section: section.clone(),
+                    line,
+                    severity: rule.severity,
+                    rule: rule.id,
+                    message,
+                })
+        })
+        .collect()
+}
+
+pub fn lint_document(sections: &[(ContentListItem, Vec<String>)]) -> Vec<Diagnostic> {
+    sections
+        .iter()
+        .flat_map(|(section, lines)| lint_section(section, lines))
+        .collect()
+}
+
+pub fn run(
+    path: &Path,
+    markdown_style: &MarkdownStyle,
+    stats_targets: &StatsTargets,
+    lint_rules: &LintRules,
+    definitions: &SectionDefinitions,
+) -> Result<bool> {
+    let data =
+        fs::read_to_string(path).wrap_err_with(|| format!("Unable to read {}", path.display()))?;
+    let lines: Vec<String> = data.lines().map(|line| line.to_string()).collect();
+    let parsed = document::parse(&lines, definitions);
+    let sections: Vec<(ContentListItem, Vec<String>)> = parsed
+        .iter()
+        .map(|section| (section.section.clone(), section.lines.clone()))
+        .collect();
+    let mut diagnostics = lint_document(&sections);
+    diagnostics.extend(markdown_style.check_document(&sections));
+    diagnostics.extend(stats_targets.check_document(&sections));
+    diagnostics.extend(
+        TemplateFingerprints::new(Content::with_definitions(definitions.clone()).templates())
+            .check_document(&sections),
+    );
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    diagnostics.extend(LinkChecker::new(&lines, base_dir).check_document(&sections));
+    let diagnostics = lint_rules.apply(diagnostics);
+
+    let mut failed = false;
+    for diagnostic in &diagnostics {
+        let first_line = parsed
+            .iter()
+            .find(|section| section.section == diagnostic.section)
+            .map_or(0, |section| section.first_line);
+        let line_number = match diagnostic.line {
+            Some(line) => first_line + line + 1,
+            None => first_line.max(1),
+        };
+        println!(
+            "{}:{}: {}[{}] {:?}: {}",
+            path.display(),
+            line_number,
+            diagnostic.severity,
+            diagnostic.rule,
+            diagnostic.section,
+            diagnostic.message
+        );
+        failed |= diagnostic.severity == Severity::Error || lint_rules.deny_warnings;
+    }
+    println!(
+        "{} problem(s) found in {}",
+        diagnostics.len(),
+        path.display()
+    );
+    Ok(!failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+
+    fn rules(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.rule)
+            .collect()
+    }
+
+    #[test]
+    fn test_tutorial_needs_steps_and_objective() {
+        let diagnostics = lint_section(
+            &ContentListItem::Tutorials,
+            &lines(&["1. **[Tutorial 1](#tutorial-1)**", "Some text"]),
+        );
+        assert_eq!(
+            rules(&diagnostics),
+            vec!["tutorial-numbered-steps", "tutorial-objective"]
+        );
+
+        let diagnostics = lint_section(
+            &ContentListItem::Tutorials,
+            &lines(&["#### Objective", "Learn things", "1. Run `cargo run`"]),
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_guides_start_from_goal() {
+        let diagnostics = lint_section(&ContentListItem::Guides, &lines(&["Steps:"]));
+        assert_eq!(rules(&diagnostics), vec!["guide-goal"]);
+
+        let diagnostics = lint_section(
+            &ContentListItem::Guides,
+            &lines(&["### How to release a new version"]),
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_reference_avoids_second_person_and_narrative() {
+        let diagnostics = lint_section(
+            &ContentListItem::Reference,
+            &lines(&[
+                "| Flag | Default |",
+                "First, open your terminal.",
+                "```",
+                "you can ignore code",
+                "```",
+            ]),
+        );
+        assert_eq!(
+            rules(&diagnostics),
+            vec!["reference-second-person", "reference-narrative"]
+        );
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.line == Some(1)));
+    }
+
+    #[test]
+    fn test_explanation_avoids_step_lists() {
+        let diagnostics = lint_section(
+            &ContentListItem::Explanation,
+            &lines(&[
+                "1. **[Architecture](#architecture)**",
+                "Why we chose Rust.",
+                "2. Install the toolchain",
+            ]),
+        );
+        assert_eq!(rules(&diagnostics), vec!["explanation-step-list"]);
+        assert_eq!(diagnostics[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_empty_project_name_is_an_error() {
+        let diagnostics = lint_section(&ContentListItem::ProjectName, &lines(&[""]));
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+}
@@ -1,11 +1,60 @@
 mod app;
+pub mod build;
+pub mod config;
 pub mod content;
+pub mod diagnostics;
+pub mod document;
 mod events;
+pub mod export;
+mod external_editor;
+pub mod front_matter;
+pub mod includes;
+pub mod init;
+pub mod keymap;
+pub mod links;
+pub mod lint;
+pub mod markdown;
+pub mod metadata;
+pub mod outline;
+pub mod packs;
+pub mod placeholders;
 pub mod popup;
 pub mod screen;
+pub mod search;
+pub mod sections;
+pub mod snippets;
+pub mod source;
+pub mod spell;
+pub mod stats;
+pub mod templates;
+#[cfg(test)]
+mod test_support;
+pub mod theme;
+pub mod verify;
+pub mod vim;
 use app::App;
-use clap::Parser;
-use color_eyre::eyre::Result;
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::{eyre, Result};
+use config::LoadedConfig;
+use content::Content;
+use document::RenderOptions;
+use export::Exports;
+use includes::Includes;
+use keymap::Keymap;
+use lint::LintRules;
+use markdown::MarkdownStyle;
+use sections::{section_key, SectionDefinitions};
+use snippets::SnippetLibrary;
+use source::{DocumentSource, SavedDocument};
+use spell::SpellChecker;
+use stats::StatsTargets;
+use std::{
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+use theme::Theme;
 
 #[derive(Parser)]
 #[command(author, about, long_about = None)]
@@ -13,30 +62,408 @@ struct Cli {
     #[arg(short = 'v', long)]
     version: bool,
 
-    #[arg(short = 'o', long, default_value = "txtui-output")]
-    output_dir: String,
+    #[command(subcommand)]
+    command: Option<Commands>,
 
-    #[arg(short = 'f', long, default_value = "README.md")]
-    file_name: String,
+    #[arg(short = 'o', long, global = true)]
+    output_dir: Option<PathBuf>,
+
+    #[arg(short = 'f', long, global = true)]
+    file_name: Option<String>,
+
+    #[arg(long, global = true)]
+    template_dir: Option<PathBuf>,
+
+    #[arg(long, global = true)]
+    no_mouse: bool,
+
+    #[arg(long, global = true)]
+    vim: bool,
+
+    #[arg(long, global = true)]
+    toc: bool,
+
+    #[arg(long, global = true, default_value_t = 4, value_parser = clap::value_parser!(u8).range(2..=6))]
+    toc_depth: u8,
+
+    #[arg(long, global = true)]
+    keep_heading_levels: bool,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Scaffold section templates and a project config in the current directory
+    Init {
+        #[arg(long)]
+        force: bool,
+
+        #[arg(long)]
+        pack: Option<String>,
+    },
+    /// Open the interactive editor (default)
+    Edit,
+    /// Render the outputs again from the saved document without opening the editor
+    Build {
+        file: Option<PathBuf>,
+
+        #[arg(long)]
+        check: bool,
+    },
+    /// Check a generated document against the Diátaxis lint rules
+    Lint {
+        file: Option<PathBuf>,
+
+        #[arg(long)]
+        deny_warnings: bool,
+    },
+    /// Run the runnable shell snippets from Tutorials and Guides
+    Verify {
+        file: Option<PathBuf>,
+
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+    },
+    /// List, check or preview the section templates
+    Templates {
+        #[command(subcommand)]
+        command: TemplatesCommand,
+    },
+    /// List or install template packs
+    Packs {
+        #[command(subcommand)]
+        command: PacksCommand,
+    },
+    /// Inspect the merged configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum TemplatesCommand {
+    /// Show where each section template is read from
+    List,
+    /// Report missing templates, unknown variables and malformed front matter
+    Validate,
+    /// Print the document the templates produce with variables filled in
+    Render,
+}
+
+#[derive(Subcommand)]
+enum PacksCommand {
+    /// Show the built-in and installed template packs
+    List,
+    /// Install a template pack from a directory or tarball
+    Install { path: PathBuf },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the effective configuration and where each value came from
+    Show,
 }
 
 pub struct CliConfig {
-    pub output_dir: String,
-    pub file_name: String,
+    pub exports: Exports,
+    pub source: DocumentSource,
+    pub saved_document: Option<SavedDocument>,
+    pub mouse_capture: bool,
+    pub vim: bool,
+    pub render_options: RenderOptions,
+    pub markdown_style: MarkdownStyle,
+    pub spell_checker: SpellChecker,
+    pub stats_targets: StatsTargets,
+    pub lint_rules: LintRules,
+    pub theme: Theme,
+    pub keymap: Keymap,
+    pub snippets: SnippetLibrary,
+    pub includes: Includes,
+    pub project_config: PathBuf,
 }
+
+fn config_defaults() -> Vec<(String, toml::Value)> {
+    let exports = Exports::default();
+    let definitions = SectionDefinitions::default();
+    let markdown_style = MarkdownStyle::default();
+    let spell_checker = SpellChecker::default();
+    let theme = Theme::default();
+    let mut defaults: Vec<(String, toml::Value)> = vec![
+        (
+            "output.dir".to_string(),
+            exports.output_dir.display().to_string().into(),
+        ),
+        ("output.file_name".to_string(), exports.file_name.into()),
+        ("source.dir".to_string(), source::DEFAULT_SOURCE_DIR.into()),
+        (
+            "document.order".to_string(),
+            toml::Value::Array(
+                definitions
+                    .order_keys()
+                    .into_iter()
+                    .map(|key| key.into())
+                    .collect(),
+            ),
+        ),
+        (
+            "templates.dir".to_string(),
+            definitions.template_dir.display().to_string().into(),
+        ),
+        (
+            "includes.dir".to_string(),
+            Includes::default().base_dir.display().to_string().into(),
+        ),
+        (
+            "export.formats".to_string(),
+            toml::Value::Array(
+                exports
+                    .formats
+                    .iter()
+                    .map(|format| format.name().into())
+                    .collect(),
+            ),
+        ),
+        ("lint.deny_warnings".to_string(), false.into()),
+        (
+            "markdown.format_on_save".to_string(),
+            markdown_style.format_on_save.into(),
+        ),
+        (
+            "markdown.list_marker".to_string(),
+            markdown_style.list_marker.to_string().into(),
+        ),
+        ("spell.enabled".to_string(), spell_checker.enabled.into()),
+        ("spell.language".to_string(), spell_checker.language.into()),
+        (
+            "spell.word_list".to_string(),
+            spell::DEFAULT_WORD_LIST.into(),
+        ),
+        (
+            "stats.words_per_minute".to_string(),
+            (stats::DEFAULT_WORDS_PER_MINUTE as i64).into(),
+        ),
+        ("stats.targets.tutorials.min_words".to_string(), 1.into()),
+        ("stats.targets.reference.min_words".to_string(), 100.into()),
+        (
+            "theme.border".to_string(),
+            theme.border.to_string().to_lowercase().into(),
+        ),
+        (
+            "theme.highlight".to_string(),
+            theme.highlight.to_string().to_lowercase().into(),
+        ),
+    ];
+    for section in Content::sections() {
+        let definition = definitions.get(&section);
+        let key = section_key(&section);
+        defaults.extend([
+            (
+                format!("sections.{}.heading", key),
+                definition.heading.clone().into(),
+            ),
+            (
+                format!("sections.{}.label", key),
+                definition.label.clone().into(),
+            ),
+            (
+                format!("sections.{}.template", key),
+                definition.template.clone().into(),
+            ),
+        ]);
+    }
+    defaults
+}
+
+fn cli_overrides(cli: &Cli) -> Vec<(String, toml::Value)> {
+    let mut overrides: Vec<(String, toml::Value)> = Vec::new();
+    if let Some(dir) = &cli.output_dir {
+        overrides.push(("output.dir".to_string(), dir.display().to_string().into()));
+    }
+    if let Some(file_name) = &cli.file_name {
+        overrides.push(("output.file_name".to_string(), file_name.clone().into()));
+    }
+    if let Some(dir) = &cli.template_dir {
+        overrides.push((
+            "templates.dir".to_string(),
+            dir.display().to_string().into(),
+        ));
+    }
+    overrides
+}
+
+fn show_config(configs: &[LoadedConfig], cli: &Cli) -> Result<()> {
+    if configs.is_empty() {
+        println!("# no config files found");
+    }
+    for config in configs {
+        println!("# loaded {}", config.path.display());
+    }
+    for (key, (value, source)) in
+        config::effective_values(configs, config_defaults(), cli_overrides(cli))?
+    {
+        println!("{} = {}  # {}", key, value, source);
+    }
+    Ok(())
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    let output_dir = cli.output_dir;
-    let file_name = cli.file_name;
+    let mouse_capture = !cli.no_mouse;
+    let vim = cli.vim;
 
     if cli.version {
         println!("txtui version {}", env!("CARGO_PKG_VERSION"));
         return Ok(());
     }
     color_eyre::install()?;
+    let configs = config::load_configs()?;
+    if let Some(Commands::Config {
+        command: ConfigCommand::Show,
+    }) = cli.command
+    {
+        return show_config(&configs, &cli);
+    }
+    let overrides = cli_overrides(&cli);
+    let exports = Exports::from_configs(&configs, cli.output_dir, cli.file_name)?;
+    let source = DocumentSource::from_configs(&configs)?;
+    let render_options = RenderOptions {
+        toc_depth: cli.toc.then_some(cli.toc_depth as usize),
+        normalize_headings: !cli.keep_heading_levels,
+        sections: SectionDefinitions::from_configs(&configs, cli.template_dir)?,
+    };
+    let markdown_style = MarkdownStyle::from_configs(&configs)?;
+    let stats_targets = StatsTargets::from_configs(&configs)?;
+    let mut lint_rules = LintRules::from_configs(&configs)?;
+    let theme = Theme::from_configs(&configs)?;
+    let includes = Includes::from_configs(&configs)?;
+    let default_path = exports.readme_path();
+    match cli.command {
+        Some(Commands::Lint {
+            file,
+            deny_warnings,
+        }) => {
+            let path = file.unwrap_or(default_path);
+            lint_rules.deny_warnings |= deny_warnings;
+            if !lint::run(
+                &path,
+                &markdown_style,
+                &stats_targets,
+                &lint_rules,
+                &render_options.sections,
+            )? {
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Commands::Verify { file, timeout }) => {
+            let path = file.unwrap_or(default_path);
+            if !verify::run(
+                &path,
+                &render_options.sections,
+                Duration::from_secs(timeout),
+            )? {
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Commands::Init { force, pack }) => {
+            let pack = packs::find(pack.as_deref().unwrap_or(packs::DEFAULT_PACK))?;
+            init::run(Path::new("."), force, &pack)?;
+            return Ok(());
+        }
+        Some(Commands::Templates { command }) => {
+            let definitions = &render_options.sections;
+            match command {
+                TemplatesCommand::List => templates::list(
+                    definitions,
+                    &config::effective_values(&configs, config_defaults(), overrides)?,
+                ),
+                TemplatesCommand::Validate => {
+                    if !templates::validate(definitions)? {
+                        process::exit(1);
+                    }
+                }
+                TemplatesCommand::Render => {
+                    for line in templates::render(definitions, &render_options) {
+                        println!("{}", line);
+                    }
+                }
+            }
+            return Ok(());
+        }
+        Some(Commands::Packs {
+            command: PacksCommand::List,
+        }) => {
+            for pack in packs::available()? {
+                let location = pack
+                    .location
+                    .as_ref()
+                    .map_or("built in".to_string(), |dir| dir.display().to_string());
+                println!("{:<20} {} ({})", pack.name, pack.description, location);
+            }
+            return Ok(());
+        }
+        Some(Commands::Packs {
+            command: PacksCommand::Install { path },
+        }) => {
+            let pack = packs::install(&path)?;
+            if let Some(dir) = &pack.location {
+                println!("installed template pack {} to {}", pack.name, dir.display());
+            }
+            return Ok(());
+        }
+        Some(Commands::Build { file, check }) => {
+            let saved = source.load()?;
+            let front_matter = saved
+                .as_ref()
+                .map(|saved| saved.front_matter.clone())
+                .unwrap_or_default();
+            let sections = match (file, saved) {
+                (Some(path), _) => build::read_document(&path, &render_options, &markdown_style)?,
+                (None, Some(saved)) => saved.sections,
+                (None, None) => {
+                    build::read_document(&default_path, &render_options, &markdown_style)?
+                }
+            };
+            if !build::run(
+                &sections,
+                &front_matter,
+                &exports,
+                &includes,
+                &render_options,
+                check,
+            )? {
+                process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Commands::Config { .. }) | Some(Commands::Edit) | None => {}
+    }
+    if !io::stdout().is_terminal() {
+        return Err(eyre!(
+            "txtui edit needs a terminal - use `txtui build` or `txtui lint` in scripts"
+        ));
+    }
+    let keymap = Keymap::from_configs(&configs)?;
+    let spell_checker = SpellChecker::from_configs(&configs)?;
+    let snippets = SnippetLibrary::from_configs(&configs)?;
+    let saved_document = source.load()?;
     let mut app = App::new(CliConfig {
-        output_dir,
-        file_name,
+        exports,
+        source,
+        saved_document,
+        mouse_capture,
+        vim,
+        render_options,
+        markdown_style,
+        spell_checker,
+        stats_targets,
+        lint_rules,
+        theme,
+        keymap,
+        snippets,
+        includes,
+        project_config: config::project_config_path(),
     });
     app.run()?;
     Ok(())
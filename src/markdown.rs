@@ -0,0 +1,415 @@
+use color_eyre::eyre::{eyre, Result};
+use regex::Regex;
+use std::{collections::HashSet, sync::LazyLock};
+
+use crate::config::LoadedConfig;
+use crate::content::ContentListItem;
+use crate::diagnostics::{Diagnostic, Severity};
+
+pub const RULES: [&str; 5] = [
+    "list-marker",
+    "trailing-whitespace",
+    "multiple-blank-lines",
+    "unclosed-fence",
+    "heading-increment",
+];
+
+static LIST_ITEM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\s*)([-*+])(\s+)").expect("valid regex"));
+static HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(#{1,6})\s").expect("valid regex"));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownStyle {
+    pub format_on_save: bool,
+    pub list_marker: char,
+    disabled: HashSet<String>,
+}
+
+impl Default for MarkdownStyle {
+    fn default() -> Self {
+        Self {
+            format_on_save: false,
+            list_marker: '-',
+            disabled: HashSet::new(),
+        }
+    }
+}
+
+enum LineKind<'a> {
+    Fence,
+    Code,
+    Text(&'a str),
+}
+
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.chars().take_while(|c| *c == marker).count();
+    (length >= 3).then_some((marker, length))
+}
+
+fn closes_fence(line: &str, (marker, length): (char, usize)) -> bool {
+    fence_marker(line).is_some_and(|(found, found_length)| {
+        found == marker
+            && found_length >= length
+            && line.trim_start()[found_length..].trim().is_empty()
+    })
+}
+
+fn line_kinds(lines: &[String]) -> (Vec<LineKind<'_>>, Option<usize>) {
+    let mut open_fence: Option<(usize, (char, usize))> = None;
+    let kinds = lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| match open_fence {
+            Some((_, fence)) if closes_fence(line, fence) => {
+                open_fence = None;
+                LineKind::Fence
+            }
+            Some(_) => LineKind::Code,
+            None => match fence_marker(line) {
+                Some(fence) => {
+                    open_fence = Some((index, fence));
+                    LineKind::Fence
+                }
+                None => LineKind::Text(line),
+            },
+        })
+        .collect();
+    (kinds, open_fence.map(|(index, _)| index))
+}
+
+pub fn fenced_lines(lines: &[String]) -> Vec<bool> {
+    line_kinds(lines)
+        .0
+        .iter()
+        .map(|kind| !matches!(kind, LineKind::Text(_)))
+        .collect()
+}
+
+pub fn code_blocks(lines: &[String]) -> usize {
+    line_kinds(lines)
+        .0
+        .iter()
+        .filter(|kind| matches!(kind, LineKind::Fence))
+        .count()
+        .div_ceil(2)
+}
+
+pub fn has_unclosed_fence(lines: &[String]) -> bool {
+    line_kinds(lines).1.is_some()
+}
+
+fn is_thematic_break(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    if text.len() - trimmed.len() > 3 {
+        return false;
+    }
+    let mut markers = trimmed.chars().filter(|c| !c.is_whitespace());
+    let Some(marker) = markers.next().filter(|c| matches!(c, '-' | '*' | '_')) else {
+        return false;
+    };
+    let mut count = 1;
+    for c in markers {
+        if c != marker {
+            return false;
+        }
+        count += 1;
+    }
+    count >= 3
+}
+
+fn is_hard_break(kinds: &[LineKind], index: usize) -> bool {
+    let LineKind::Text(text) = kinds[index] else {
+        return false;
+    };
+    let content = text.trim_end_matches(' ');
+    text.len() - content.len() >= 2
+        && !content.trim().is_empty()
+        && content.trim_end() == content
+        && matches!(kinds.get(index + 1), Some(LineKind::Text(next)) if !next.trim().is_empty())
+}
+
+impl MarkdownStyle {
+    pub fn from_configs(configs: &[LoadedConfig]) -> Result<Self> {
+        let mut style = MarkdownStyle::default();
+        for config in configs {
+            let markdown = &config.file.markdown;
+            if let Some(format_on_save) = markdown.format_on_save {
+                style.format_on_save = format_on_save;
+            }
+            if let Some(list_marker) = &markdown.list_marker {
+                style.list_marker = match list_marker.as_str() {
+                    "-" => '-',
+                    "*" => '*',
+                    "+" => '+',
+                    _ => {
+                        return Err(eyre!(
+                            "{}: list_marker must be one of '-', '*' or '+', got '{}'",
+                            config.path.display(),
+                            list_marker
+                        ))
+                    }
+                };
+            }
+            for (rule, enabled) in &markdown.rules {
+                if !RULES.contains(&rule.as_str()) {
+                    return Err(eyre!(
+                        "{}: unknown markdown rule '{}' (available: {})",
+                        config.path.display(),
+                        rule,
+                        RULES.join(", ")
+                    ));
+                }
+                if *enabled {
+                    style.disabled.remove(rule);
+                } else {
+                    style.disabled.insert(rule.clone());
+                }
+            }
+        }
+        Ok(style)
+    }
+
+    fn enabled(&self, rule: &str) -> bool {
+        !self.disabled.contains(rule)
+    }
+
+    pub fn check_section(&self, section: &ContentListItem, lines: &[String]) -> Vec<Diagnostic> {
+        let mut findings: Vec<(usize, &'static str, String)> = Vec::new();
+        let (kinds, open_fence) = line_kinds(lines);
+        let mut previous_level = 2;
+        let mut previous_blank = false;
+        for (index, (line, kind)) in lines.iter().zip(&kinds).enumerate() {
+            if line.trim_end() != line
+                && !matches!(kind, LineKind::Code)
+                && !is_hard_break(&kinds, index)
+            {
+                findings.push((
+                    index,
+                    "trailing-whitespace",
+                    "Trailing whitespace".to_string(),
+                ));
+            }
+            let LineKind::Text(text) = kind else {
+                previous_blank = false;
+                continue;
+            };
+            let blank = text.trim().is_empty();
+            if blank && previous_blank {
+                findings.push((
+                    index,
+                    "multiple-blank-lines",
+                    "Multiple consecutive blank lines".to_string(),
+                ));
+            }
+            previous_blank = blank;
+            if let Some(captures) = LIST_ITEM
+                .captures(text)
+                .filter(|_| !is_thematic_break(text))
+            {
+                let marker = &captures[2];
+                if !marker.starts_with(self.list_marker) {
+                    findings.push((
+                        index,
+                        "list-marker",
+                        format!(
+                            "Use '{}' instead of '{}' for list items",
+                            self.list_marker, marker
+                        ),
+                    ));
+                }
+            }
+            if let Some(captures) = HEADING.captures(text) {
+                let level = captures[1].len();
+                if level > previous_level + 1 {
+                    findings.push((
+                        index,
+                        "heading-increment",
+                        format!("Heading level jumps from h{} to h{}", previous_level, level),
+                    ));
+                }
+                previous_level = level;
+            }
+        }
+        if let Some(index) = open_fence {
+            findings.push((
+                index,
+                "unclosed-fence",
+                "Code fence is never closed".to_string(),
+            ));
+        }
+        findings
+            .into_iter()
+            .filter(|(_, rule, _)| self.enabled(rule))
+            .map(|(line, rule, message)| Diagnostic {
+                section: section.clone(),
+                line: Some(line),
+                severity: Severity::Warning,
+                rule,
+                message,
+            })
+            .collect()
+    }
+
+    pub fn check_document(&self, sections: &[(ContentListItem, Vec<String>)]) -> Vec<Diagnostic> {
+        sections
+            .iter()
+            .flat_map(|(section, lines)| self.check_section(section, lines))
+            .collect()
+    }
+
+    pub fn format_section(&self, lines: &[String]) -> Vec<String> {
+        let (kinds, open_fence) = line_kinds(lines);
+        let mut formatted: Vec<String> = Vec::with_capacity(lines.len());
+        let mut previous_blank = false;
+        for (index, (line, kind)) in lines.iter().zip(&kinds).enumerate() {
+            let mut line = line.clone();
+            if self.enabled("trailing-whitespace")
+                && !matches!(kind, LineKind::Code)
+                && !is_hard_break(&kinds, index)
+            {
+                line.truncate(line.trim_end().len());
+            }
+            if let LineKind::Text(_) = kind {
+                let blank = line.trim().is_empty();
+                if blank && previous_blank && self.enabled("multiple-blank-lines") {
+                    continue;
+                }
+                previous_blank = blank;
+                if self.enabled("list-marker") && !is_thematic_break(&line) {
+                    line = LIST_ITEM
+                        .replace(&line, |captures: &regex::Captures| {
+                            format!("{}{}{}", &captures[1], self.list_marker, &captures[3])
+                        })
+                        .into_owned();
+                }
+            } else {
+                previous_blank = false;
+            }
+            formatted.push(line);
+        }
+        if let Some(index) = open_fence.filter(|_| self.enabled("unclosed-fence")) {
+            let (marker, length) = fence_marker(&lines[index]).unwrap_or(('`', 3));
+            formatted.push(marker.to_string().repeat(length));
+        }
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFile;
+    use crate::test_support::lines;
+    use std::path::PathBuf;
+
+    fn rules(diagnostics: &[Diagnostic]) -> Vec<(Option<usize>, &str)> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.rule))
+            .collect()
+    }
+
+    #[test]
+    fn test_style_problems_are_reported() {
+        let diagnostics = MarkdownStyle::default().check_section(
+            &ContentListItem::Guides,
+            &lines(&[
+                "#### Skipped",
+                "* item  ",
+                "",
+                "",
+                "**bold** text",
+                "```sh",
+                "* not a list",
+            ]),
+        );
+        assert_eq!(
+            rules(&diagnostics),
+            vec![
+                (Some(0), "heading-increment"),
+                (Some(1), "trailing-whitespace"),
+                (Some(1), "list-marker"),
+                (Some(3), "multiple-blank-lines"),
+                (Some(5), "unclosed-fence"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_normalizes_markdown() {
+        let formatted = MarkdownStyle::default().format_section(&lines(&[
+            "* one ", "  + two", "", "", "", "```", "* code", "", "",
+        ]));
+        assert_eq!(
+            formatted,
+            lines(&["- one", "  - two", "", "```", "* code", "", "", "```"])
+        );
+    }
+
+    #[test]
+    fn test_format_keeps_code_hard_breaks_and_thematic_breaks() {
+        let formatted = MarkdownStyle::default().format_section(&lines(&[
+            "First line  ",
+            "continues here ",
+            "* * *",
+            "````md",
+            "```",
+            "keep   ",
+            "~~~",
+            "````",
+            "~~~",
+            "* tilde code",
+        ]));
+        assert_eq!(
+            formatted,
+            lines(&[
+                "First line  ",
+                "continues here",
+                "* * *",
+                "````md",
+                "```",
+                "keep   ",
+                "~~~",
+                "````",
+                "~~~",
+                "* tilde code",
+                "~~~",
+            ])
+        );
+        assert_eq!(
+            fenced_lines(&lines(&["a", "```", "b", "~~~", "```", "c"])),
+            vec![false, true, true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn test_rules_are_configurable() {
+        let file: ConfigFile = toml::from_str(
+            "[markdown]\nformat_on_save = true\nlist_marker = \"*\"\n[markdown.rules]\ntrailing-whitespace = false",
+        )
+        .unwrap();
+        let config = LoadedConfig {
+            path: PathBuf::from(".txtui.toml"),
+            file,
+        };
+        let style = MarkdownStyle::from_configs(&[config]).unwrap();
+        assert!(style.format_on_save);
+        assert_eq!(
+            style.format_section(&lines(&["- item  "])),
+            lines(&["* item  "])
+        );
+
+        let file: ConfigFile = toml::from_str("[markdown.rules]\nno-such-rule = false").unwrap();
+        let config = LoadedConfig {
+            path: PathBuf::from(".txtui.toml"),
+            file,
+        };
+        assert!(MarkdownStyle::from_configs(&[config]).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+pub const CARGO_MANIFEST: &str = "Cargo.toml";
+pub const LISTS: [(&str, &[&str]); 2] = [("binaries", &["name", "path"]), ("features", &["name"])];
+
+pub type Item = BTreeMap<String, String>;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectMetadata {
+    pub variables: BTreeMap<String, String>,
+    pub lists: BTreeMap<String, Vec<Item>>,
+}
+
+fn item(fields: &[(&str, &str)]) -> Item {
+    fields
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn text<'a>(table: &'a toml::Table, key: &str) -> &'a str {
+    table
+        .get(key)
+        .and_then(toml::Value::as_str)
+        .unwrap_or_default()
+}
+
+fn binaries(dir: &Path, cargo: &toml::Table, package_name: &str) -> Vec<Item> {
+    let mut binaries: Vec<Item> = cargo
+        .get("bin")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_table)
+        .map(|bin| {
+            let name = text(bin, "name");
+            let default_path = format!("src/bin/{}.rs", name);
+            let path = bin
+                .get("path")
+                .and_then(toml::Value::as_str)
+                .unwrap_or(&default_path);
+            item(&[("name", name), ("path", path)])
+        })
+        .collect();
+    let has_bin = |name: &str| binaries.iter().any(|bin| bin["name"] == name);
+    let mut found = Vec::new();
+    if dir.join("src/main.rs").is_file() && !package_name.is_empty() && !has_bin(package_name) {
+        found.push(item(&[("name", package_name), ("path", "src/main.rs")]));
+    }
+    if let Ok(entries) = fs::read_dir(dir.join("src/bin")) {
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let Some(name) = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+            else {
+                continue;
+            };
+            if !has_bin(&name) {
+                found.push(item(&[
+                    ("name", &name),
+                    ("path", &format!("src/bin/{}.rs", name)),
+                ]));
+            }
+        }
+    }
+    found.append(&mut binaries);
+    found
+}
+
+pub fn collect(dir: &Path) -> ProjectMetadata {
+    let project_name = dir
+        .canonicalize()
+        .ok()
+        .and_then(|dir| {
+            dir.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .unwrap_or_default();
+    let cargo: toml::Table = fs::read_to_string(dir.join(CARGO_MANIFEST))
+        .ok()
+        .and_then(|data| toml::from_str(&data).ok())
+        .unwrap_or_default();
+    let package = cargo
+        .get("package")
+        .and_then(toml::Value::as_table)
+        .cloned()
+        .unwrap_or_default();
+    let package_name = text(&package, "name");
+    let binaries = binaries(dir, &cargo, package_name);
+    let features: Vec<Item> = cargo
+        .get("features")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|features| features.keys())
+        .filter(|name| *name != "default")
+        .map(|name| item(&[("name", name)]))
+        .collect();
+    let library = cargo.contains_key("lib") || dir.join("src/lib.rs").is_file();
+
+    let mut metadata = ProjectMetadata::default();
+    for (key, value) in [
+        ("project_name", project_name.as_str()),
+        ("crate_name", package_name),
+        ("crate_version", text(&package, "version")),
+        ("crate_description", text(&package, "description")),
+        ("library", if library { "true" } else { "false" }),
+        ("cli", if binaries.is_empty() { "false" } else { "true" }),
+    ] {
+        metadata
+            .variables
+            .insert(key.to_string(), value.to_string());
+    }
+    metadata.lists.insert("binaries".to_string(), binaries);
+    metadata.lists.insert("features".to_string(), features);
+    metadata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    #[test]
+    fn test_cargo_metadata_is_collected() {
+        let dir = env::temp_dir().join(format!("txtui-metadata-{}", process::id()));
+        fs::create_dir_all(dir.join("src/bin")).unwrap();
+        fs::write(
+            dir.join(CARGO_MANIFEST),
+            "[package]\nname = \"tool\"\nversion = \"1.2.0\"\n\n[features]\ndefault = []\njson = []\n\n[[bin]]\nname = \"toolctl\"\npath = \"cmd/ctl.rs\"\n",
+        )
+        .unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(dir.join("src/bin/helper.rs"), "fn main() {}").unwrap();
+
+        let metadata = collect(&dir);
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(metadata.variables["crate_version"], "1.2.0");
+        assert_eq!(metadata.variables["cli"], "true");
+        assert_eq!(metadata.variables["library"], "false");
+        let binaries: Vec<(&str, &str)> = metadata.lists["binaries"]
+            .iter()
+            .map(|bin| (bin["name"].as_str(), bin["path"].as_str()))
+            .collect();
+        assert_eq!(
+            binaries,
+            vec![
+                ("tool", "src/main.rs"),
+                ("helper", "src/bin/helper.rs"),
+                ("toolctl", "cmd/ctl.rs")
+            ]
+        );
+        assert_eq!(metadata.lists["features"], vec![item(&[("name", "json")])]);
+    }
+}
@@ -0,0 +1,128 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem},
+    Frame,
+};
+
+use crate::content::ContentListItem;
+use crate::document::{self, RenderOptions};
+use crate::links;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineEntry {
+    pub section: ContentListItem,
+    pub line: Option<usize>,
+    pub level: usize,
+    pub original_level: usize,
+    pub title: String,
+}
+
+pub fn outline(
+    sections: &[(ContentListItem, Vec<String>)],
+    options: &RenderOptions,
+) -> Vec<OutlineEntry> {
+    let mut entries = Vec::new();
+    for (section, lines) in sections {
+        if section == &ContentListItem::ProjectName {
+            entries.push(OutlineEntry {
+                section: section.clone(),
+                line: None,
+                level: 1,
+                original_level: 1,
+                title: lines.first().cloned().unwrap_or_default(),
+            });
+            continue;
+        }
+        entries.push(OutlineEntry {
+            section: section.clone(),
+            line: None,
+            level: 2,
+            original_level: 2,
+            title: options.sections.heading(section).to_string(),
+        });
+        let shift = if options.normalize_headings {
+            document::heading_shift(lines)
+        } else {
+            0
+        };
+        entries.extend(
+            links::headings(lines)
+                .into_iter()
+                .map(|(index, level, title)| OutlineEntry {
+                    section: section.clone(),
+                    line: Some(index),
+                    level: document::shifted_level(level, shift),
+                    original_level: level,
+                    title: links::heading_text(&title),
+                }),
+        );
+    }
+    entries
+}
+
+pub fn render_preview(frame: &mut Frame, area: Rect, entries: &[OutlineEntry]) {
+    let items: Vec<ListItem> = entries
+        .iter()
+        .map(|entry| {
+            let mut spans = vec![Span::raw(format!(
+                "{}{}",
+                "  ".repeat(entry.level - 1),
+                entry.title
+            ))];
+            if entry.level != entry.original_level {
+                spans.push(Span::styled(
+                    format!(" h{}→h{}", entry.original_level, entry.level),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue))
+            .title(" Outline "),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(list, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+
+    #[test]
+    fn test_outline_shows_shifted_levels() {
+        let sections = vec![
+            (ContentListItem::ProjectName, lines(&["txtui"])),
+            (
+                ContentListItem::Guides,
+                lines(&["# How to build", "## Steps"]),
+            ),
+        ];
+        let levels = |options: &RenderOptions| -> Vec<(usize, usize, String)> {
+            outline(&sections, options)
+                .into_iter()
+                .map(|entry| (entry.level, entry.original_level, entry.title))
+                .collect()
+        };
+        assert_eq!(
+            levels(&RenderOptions::default()),
+            vec![
+                (1, 1, "txtui".to_string()),
+                (2, 2, "Guides".to_string()),
+                (3, 1, "How to build".to_string()),
+                (4, 2, "Steps".to_string()),
+            ]
+        );
+        let options = RenderOptions {
+            normalize_headings: false,
+            ..RenderOptions::default()
+        };
+        assert_eq!(levels(&options)[2], (1, 1, "How to build".to_string()));
+    }
+}
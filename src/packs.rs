@@ -0,0 +1,573 @@
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use ratatui::{
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::content::{Content, ContentListItem};
+use crate::init;
+use crate::links::slugify;
+use crate::sections::{section_for_name, SectionDefinitions};
+
+pub const DEFAULT_PACK: &str = "default";
+pub const PACK_FILE: &str = "pack.toml";
+const PROJECT_NAME_SCAFFOLD: &str = "{{project_name}}\n";
+pub const BUILTIN_PACKS: [(&str, &str); 5] = [
+    (
+        "rust-library",
+        include_str!("../templates/packs/rust-library.toml"),
+    ),
+    ("cli-tool", include_str!("../templates/packs/cli-tool.toml")),
+    (
+        "web-service",
+        include_str!("../templates/packs/web-service.toml"),
+    ),
+    (
+        "data-pipeline",
+        include_str!("../templates/packs/data-pipeline.toml"),
+    ),
+    (
+        "internal-platform",
+        include_str!("../templates/packs/internal-platform.toml"),
+    ),
+];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PackSection {
+    #[serde(default)]
+    pages: Vec<String>,
+    #[serde(default)]
+    headings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PackManifest {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    sections: HashMap<String, PackSection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pack {
+    pub name: String,
+    pub description: String,
+    pub location: Option<PathBuf>,
+    templates: HashMap<ContentListItem, String>,
+}
+
+struct SectionLayout {
+    purpose: &'static str,
+    contents_title: &'static str,
+    page_prefix: &'static str,
+    page_body: &'static [&'static str],
+}
+
+fn section_layout(section: &ContentListItem) -> Option<SectionLayout> {
+    match section {
+        ContentListItem::ProjectName => None,
+        ContentListItem::Tutorials => Some(SectionLayout {
+            purpose: "Step-by-step lessons designed to help new users get started with the project.",
+            contents_title: "Tutorials",
+            page_prefix: "Tutorial",
+            page_body: &[
+                "#### Objective",
+                "> Explain what users will learn by the end of this tutorial.",
+                "",
+                "#### Prerequisites",
+                " - List any prerequisites (software, accounts, access) needed.",
+                "",
+                "#### Steps",
+                "1. **Step 1**: Describe the first step in detail.",
+                "2. **Step 2**: Final steps to complete the tutorial.",
+                "",
+                "#### Summary",
+                "> Briefly recap what was covered in this tutorial.",
+            ],
+        }),
+        ContentListItem::Guides => Some(SectionLayout {
+            purpose: "Provide focused guidance for achieving specific tasks within the project.",
+            contents_title: "How-To Guides",
+            page_prefix: "Guide",
+            page_body: &[
+                "#### Goal",
+                "> What users will accomplish by following this guide.",
+                "",
+                "#### Steps",
+                "1. **Step 1**: Describe actions needed for the task.",
+                "2. **Step 2**: Complete the task, noting any additional resources or considerations.",
+            ],
+        }),
+        ContentListItem::Explanation => Some(SectionLayout {
+            purpose: "Provide conceptual information to deepen user understanding.",
+            contents_title: "Explanations",
+            page_prefix: "Explanation",
+            page_body: &[
+                "#### Overview",
+                "> Briefly introduce the concept and its importance within the project.",
+                "",
+                "#### Details",
+                "- **Key Concept 1**: Explanation of the first key concept.",
+            ],
+        }),
+        ContentListItem::Reference => Some(SectionLayout {
+            purpose: "Technical descriptions of the project, kept accurate and up to date.",
+            contents_title: "Reference",
+            page_prefix: "Reference",
+            page_body: &["> Describe this part of the project."],
+        }),
+    }
+}
+
+fn scaffold(section: &ContentListItem, pack_section: &PackSection) -> String {
+    let Some(layout) = section_layout(section) else {
+        return PROJECT_NAME_SCAFFOLD.to_string();
+    };
+    let mut lines = vec![
+        "### Purpose".to_string(),
+        format!("> {}", layout.purpose),
+        String::new(),
+    ];
+    let titles: Vec<String> = pack_section
+        .pages
+        .iter()
+        .enumerate()
+        .map(|(index, page)| format!("{} {}: {}", layout.page_prefix, index + 1, page))
+        .collect();
+    if !titles.is_empty() {
+        lines.push(format!("### {}", layout.contents_title));
+        for (index, title) in titles.iter().enumerate() {
+            lines.push(format!(
+                "{}. **[{}](#{})**",
+                index + 1,
+                title,
+                slugify(title)
+            ));
+        }
+        lines.extend([String::new(), "---".to_string(), String::new()]);
+    }
+    for title in &titles {
+        lines.push(format!("### {}", title));
+        lines.push(String::new());
+        lines.extend(layout.page_body.iter().map(|line| line.to_string()));
+        lines.push(String::new());
+    }
+    for heading in &pack_section.headings {
+        lines.push(format!("### {}", heading));
+        lines.push(format!("> Describe {} here.", heading.to_lowercase()));
+        lines.push(String::new());
+    }
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn install_dir() -> Option<PathBuf> {
+    if let Ok(data_home) = env::var("XDG_DATA_HOME") {
+        if !data_home.is_empty() {
+            return Some(Path::new(&data_home).join("txtui").join("packs"));
+        }
+    }
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    Some(Path::new(&home).join(".local/share/txtui/packs"))
+}
+
+impl Pack {
+    pub fn default_pack() -> Self {
+        Self {
+            name: DEFAULT_PACK.to_string(),
+            description: "Generic Diátaxis templates".to_string(),
+            location: None,
+            templates: Content::sections()
+                .into_iter()
+                .zip(init::TEMPLATES.map(|(_, contents)| contents.to_string()))
+                .collect(),
+        }
+    }
+
+    fn parse(name: &str, data: &str, location: Option<&Path>) -> Result<Self> {
+        let origin = location.map_or_else(
+            || format!("built-in pack {}", name),
+            |dir| dir.join(PACK_FILE).display().to_string(),
+        );
+        let manifest: PackManifest =
+            toml::from_str(data).wrap_err_with(|| format!("Invalid pack manifest {}", origin))?;
+        let mut pack_sections: HashMap<&ContentListItem, &PackSection> = HashMap::new();
+        for (key, pack_section) in &manifest.sections {
+            let section = section_for_name(key)
+                .ok_or_else(|| eyre!("{}: unknown section '{}'", origin, key))?;
+            pack_sections.insert(section, pack_section);
+        }
+        let file_names = SectionDefinitions::default();
+        let mut templates = HashMap::new();
+        for section in Content::sections() {
+            let file = location.map(|dir| dir.join(&file_names.get(&section).template));
+            let template = match file.filter(|file| file.is_file()) {
+                Some(file) => fs::read_to_string(&file)
+                    .wrap_err_with(|| format!("Unable to read {}", file.display()))?,
+                None => scaffold(
+                    &section,
+                    pack_sections
+                        .get(&section)
+                        .copied()
+                        .unwrap_or(&Default::default()),
+                ),
+            };
+            templates.insert(section, template);
+        }
+        Ok(Self {
+            name: name.to_string(),
+            description: manifest.description,
+            location: location.map(Path::to_path_buf),
+            templates,
+        })
+    }
+
+    pub fn load(dir: &Path) -> Result<Self> {
+        let manifest = dir.join(PACK_FILE);
+        let data = fs::read_to_string(&manifest)
+            .wrap_err_with(|| format!("Unable to read {}", manifest.display()))?;
+        let name = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| eyre!("{} has no pack name", dir.display()))?;
+        Pack::parse(&name, &data, Some(dir))
+    }
+
+    pub fn template(&self, section: &ContentListItem) -> &str {
+        self.templates.get(section).map_or("", String::as_str)
+    }
+
+    pub fn write_templates(
+        &self,
+        definitions: &SectionDefinitions,
+        force: bool,
+    ) -> Result<Vec<(PathBuf, bool)>> {
+        Content::sections()
+            .iter()
+            .map(|section| {
+                let path = definitions.template_path(section);
+                let written = init::write_file(&path, self.template(section), force)?;
+                Ok((path, written))
+            })
+            .collect()
+    }
+}
+
+fn installed_packs(dir: &Path) -> Result<Vec<Pack>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(PACK_FILE).is_file())
+        .collect();
+    dirs.sort();
+    dirs.iter().map(|dir| Pack::load(dir)).collect()
+}
+
+fn available_in(packs_dir: Option<&Path>) -> Result<Vec<Pack>> {
+    let mut packs = vec![Pack::default_pack()];
+    for (name, data) in BUILTIN_PACKS {
+        packs.push(Pack::parse(name, data, None)?);
+    }
+    if let Some(dir) = packs_dir {
+        for pack in installed_packs(dir)? {
+            packs.retain(|existing| existing.name != pack.name);
+            packs.push(pack);
+        }
+    }
+    Ok(packs)
+}
+
+pub fn available() -> Result<Vec<Pack>> {
+    available_in(install_dir().as_deref())
+}
+
+pub fn find(name: &str) -> Result<Pack> {
+    find_in(name, available()?)
+}
+
+fn find_in(name: &str, packs: Vec<Pack>) -> Result<Pack> {
+    let names: Vec<&str> = packs.iter().map(|pack| pack.name.as_str()).collect();
+    let message = format!(
+        "unknown template pack '{}' (available: {})",
+        name,
+        names.join(", ")
+    );
+    packs
+        .into_iter()
+        .find(|pack| pack.name == name)
+        .ok_or_else(|| eyre!(message))
+}
+
+fn pack_root(dir: &Path) -> Option<PathBuf> {
+    if dir.join(PACK_FILE).is_file() {
+        return Some(dir.to_path_buf());
+    }
+    let mut candidates = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(PACK_FILE).is_file());
+    let root = candidates.next()?;
+    candidates.next().is_none().then_some(root)
+}
+
+fn archive_name(archive: &Path) -> String {
+    let file_name = archive
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    [".tar.gz", ".tgz", ".tar.bz2", ".tar.xz", ".tar"]
+        .iter()
+        .find_map(|extension| file_name.strip_suffix(extension))
+        .unwrap_or(&file_name)
+        .to_string()
+}
+
+fn is_slug(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+fn copy_pack(from: &Path, to: &Path) -> Result<()> {
+    if to.exists() {
+        fs::remove_dir_all(to).wrap_err_with(|| format!("Unable to replace {}", to.display()))?;
+    }
+    fs::create_dir_all(to).wrap_err_with(|| format!("Unable to create {}", to.display()))?;
+    for entry in
+        fs::read_dir(from).wrap_err_with(|| format!("Unable to read {}", from.display()))?
+    {
+        let path = entry?.path();
+        let is_pack_file = path.file_name().is_some_and(|name| name == PACK_FILE)
+            || path.extension().is_some_and(|extension| extension == "md");
+        if path.is_file() && is_pack_file {
+            if let Some(file_name) = path.file_name() {
+                fs::copy(&path, to.join(file_name))
+                    .wrap_err_with(|| format!("Unable to copy {}", path.display()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn install_into(path: &Path, packs_dir: &Path) -> Result<Pack> {
+    let staging_dir = tempfile::Builder::new()
+        .prefix("txtui-pack-")
+        .tempdir()
+        .wrap_err("Unable to create a directory to unpack the template pack")?;
+    let staging = staging_dir.path();
+    let (root, name) = if path.is_dir() {
+        let root = path
+            .canonicalize()
+            .wrap_err_with(|| format!("Unable to read {}", path.display()))?;
+        let name = root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        (root, name)
+    } else if path.is_file() {
+        let status = Command::new("tar")
+            .arg("-xf")
+            .arg(path)
+            .arg("-C")
+            .arg(staging)
+            .status()
+            .wrap_err("Unable to run tar to unpack the template pack")?;
+        if !status.success() {
+            return Err(eyre!(
+                "tar could not unpack {} ({})",
+                path.display(),
+                status
+            ));
+        }
+        let root = pack_root(staging).ok_or_else(|| {
+            eyre!(
+                "{} does not contain a single template pack with a {}",
+                path.display(),
+                PACK_FILE
+            )
+        })?;
+        let name = if root == staging {
+            archive_name(path)
+        } else {
+            root.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+        (root, name)
+    } else {
+        return Err(eyre!(
+            "{} is not a template pack directory or archive",
+            path.display()
+        ));
+    };
+    if !is_slug(&name) || name == DEFAULT_PACK {
+        return Err(eyre!(
+            "'{}' cannot be used as a template pack name (use lowercase letters, digits, '-' and '_')",
+            name
+        ));
+    }
+    let data = fs::read_to_string(root.join(PACK_FILE))
+        .wrap_err_with(|| format!("{} has no {}", path.display(), PACK_FILE))?;
+    Pack::parse(&name, &data, Some(&root))?;
+    let target = packs_dir.join(&name);
+    copy_pack(&root, &target)?;
+    Pack::load(&target)
+}
+
+pub fn install(path: &Path) -> Result<Pack> {
+    let packs_dir =
+        install_dir().ok_or_else(|| eyre!("Unable to find a data directory to install into"))?;
+    install_into(path, &packs_dir)
+}
+
+#[derive(Default)]
+pub struct PackPicker {
+    pub active: bool,
+    packs: Vec<Pack>,
+    state: ListState,
+}
+
+impl PackPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, packs: Vec<Pack>) {
+        self.active = !packs.is_empty();
+        self.packs = packs;
+        self.state.select(Some(0));
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.packs.clear();
+    }
+
+    pub fn next(&mut self) {
+        let selected = self.state.selected().unwrap_or(0);
+        self.state.select(Some((selected + 1) % self.packs.len()));
+    }
+
+    pub fn previous(&mut self) {
+        let selected = self.state.selected().unwrap_or(0);
+        self.state
+            .select(Some((selected + self.packs.len() - 1) % self.packs.len()));
+    }
+
+    pub fn selected(&self) -> Option<&Pack> {
+        self.packs.get(self.state.selected()?)
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .packs
+            .iter()
+            .map(|pack| {
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{:<20}", pack.name)),
+                    Span::styled(pack.description.clone(), Style::default().fg(Color::Gray)),
+                ]))
+            })
+            .collect();
+        let height = (items.len() as u16 + 2).min(area.height);
+        let [popup_area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(Flex::Center)
+            .areas(popup_area);
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Blue))
+                    .title(" Choose a template pack - Enter to use, ESC for none "),
+            )
+            .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black));
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(list, popup_area, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn test_builtin_packs_scaffold_pages_and_headings() {
+        let packs = available_in(None).unwrap();
+        assert!(BUILTIN_PACKS
+            .iter()
+            .all(|(name, _)| packs.iter().any(|pack| pack.name == *name)));
+
+        let pack = find_in("cli-tool", packs.clone()).unwrap();
+        let guides = pack.template(&ContentListItem::Guides);
+        assert!(guides
+            .contains("1. **[Guide 1: Configuring the tool](#guide-1-configuring-the-tool)**"));
+        assert!(guides.contains("### Guide 3: Upgrading to a new release"));
+        assert!(pack
+            .template(&ContentListItem::Reference)
+            .contains("### Exit codes\n> Describe exit codes here."));
+        assert_eq!(
+            pack.template(&ContentListItem::ProjectName),
+            PROJECT_NAME_SCAFFOLD
+        );
+        assert_eq!(
+            Pack::default_pack().template(&ContentListItem::Tutorials),
+            init::TEMPLATES[1].1
+        );
+        assert!(find_in("unknown", packs).is_err());
+    }
+
+    #[test]
+    fn test_pack_is_installed_from_directory() {
+        let root = env::temp_dir().join(format!("txtui-packs-test-{}", process::id()));
+        let source = root.join("team-pack");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(
+            source.join(PACK_FILE),
+            "description = \"Team pack\"\n[sections.guides]\nheadings = [\"On call\"]\n",
+        )
+        .unwrap();
+        fs::write(source.join("reference.md"), "### Contacts\n").unwrap();
+        fs::write(source.join("notes.txt"), "ignored").unwrap();
+
+        let pack = install_into(&source, &root.join("installed")).unwrap();
+        assert_eq!(pack.name, "team-pack");
+        assert_eq!(pack.template(&ContentListItem::Reference), "### Contacts\n");
+        assert!(pack
+            .template(&ContentListItem::Guides)
+            .contains("### On call"));
+        assert!(!root.join("installed/team-pack/notes.txt").exists());
+
+        fs::write(source.join(PACK_FILE), "[sections.intro]\n").unwrap();
+        assert!(install_into(&source, &root.join("installed")).is_err());
+
+        let unnamed = root.join("Team Pack");
+        fs::create_dir_all(&unnamed).unwrap();
+        fs::write(unnamed.join(PACK_FILE), "").unwrap();
+        assert!(install_into(&unnamed, &root.join("installed")).is_err());
+        assert!(!is_slug(&archive_name(Path::new("..tar"))));
+        assert!(root.join("installed/team-pack").is_dir());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::content::{Content, ContentListItem};
+use crate::diagnostics::{Diagnostic, Severity};
+
+const MIN_FINGERPRINT_CHARS: usize = 12;
+
+pub struct TemplateFingerprints {
+    templates: HashMap<ContentListItem, Vec<String>>,
+    fingerprints: HashSet<String>,
+}
+
+impl TemplateFingerprints {
+    pub fn new(templates: HashMap<ContentListItem, Vec<String>>) -> Self {
+        let fingerprints = templates
+            .values()
+            .flatten()
+            .filter_map(|line| fingerprint(line))
+            .collect();
+        Self {
+            templates,
+            fingerprints,
+        }
+    }
+
+    pub fn check_section(&self, section: &ContentListItem, lines: &[String]) -> Vec<Diagnostic> {
+        let template = self.templates.get(section).map(Vec::as_slice);
+        if template
+            .is_some_and(|template| !template.is_empty() && trimmed(template) == trimmed(lines))
+        {
+            return vec![Diagnostic {
+                section: section.clone(),
+                line: None,
+                severity: Severity::Error,
+                rule: "unedited-section",
+                message: "Section still contains the unchanged template".to_string(),
+            }];
+        }
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| {
+                fingerprint(line).is_some_and(|print| self.fingerprints.contains(&print))
+            })
+            .map(|(index, _)| Diagnostic {
+                section: section.clone(),
+                line: Some(index),
+                severity: Severity::Error,
+                rule: "template-text",
+                message: "Template text left unchanged".to_string(),
+            })
+            .collect()
+    }
+
+    pub fn check_document(&self, sections: &[(ContentListItem, Vec<String>)]) -> Vec<Diagnostic> {
+        Content::sections()
+            .into_iter()
+            .flat_map(
+                |section| match sections.iter().find(|(edited, _)| edited == &section) {
+                    Some((_, lines)) => self.check_section(&section, lines),
+                    None => vec![Diagnostic {
+                        section,
+                        line: None,
+                        severity: Severity::Error,
+                        rule: "unedited-section",
+                        message: "Section was never edited and is missing from the document"
+                            .to_string(),
+                    }],
+                },
+            )
+            .collect()
+    }
+}
+
+pub fn check_content(content: &Content) -> Vec<Diagnostic> {
+    TemplateFingerprints::new(content.templates()).check_document(&content.edited_sections())
+}
+
+fn fingerprint(line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.starts_with('#') || line.starts_with("```") || line.starts_with("~~~") {
+        return None;
+    }
+    let alphanumeric = line.chars().filter(|c| c.is_alphanumeric()).count();
+    (alphanumeric >= MIN_FINGERPRINT_CHARS)
+        .then(|| line.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn trimmed(lines: &[String]) -> Vec<&str> {
+    let mut lines: Vec<&str> = lines.iter().map(|line| line.trim_end()).collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+
+    fn fingerprints() -> TemplateFingerprints {
+        let mut templates = HashMap::new();
+        templates.insert(
+            ContentListItem::Tutorials,
+            lines(&[
+                "#### Objective",
+                "> Explain what users will learn by the end of this tutorial.",
+                "Steps:",
+            ]),
+        );
+        templates.insert(ContentListItem::ProjectName, Vec::new());
+        TemplateFingerprints::new(templates)
+    }
+
+    #[test]
+    fn test_unchanged_template_lines_are_flagged() {
+        let diagnostics = fingerprints().check_section(
+            &ContentListItem::Tutorials,
+            &lines(&[
+                "#### Objective",
+                ">  Explain what users will learn by the end of this tutorial.",
+                "Steps:",
+                "1. Install txtui",
+            ]),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "template-text");
+        assert_eq!(diagnostics[0].line, Some(1));
+    }
+
+    #[test]
+    fn test_untouched_and_missing_sections_are_flagged() {
+        let sections = vec![
+            (ContentListItem::ProjectName, lines(&["txtui"])),
+            (
+                ContentListItem::Tutorials,
+                lines(&[
+                    "#### Objective",
+                    "> Explain what users will learn by the end of this tutorial.",
+                    "Steps:",
+                    "",
+                ]),
+            ),
+        ];
+        let diagnostics = fingerprints().check_document(&sections);
+        let sections: Vec<(ContentListItem, &str)> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.section.clone(), diagnostic.rule))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                (ContentListItem::Tutorials, "unedited-section"),
+                (ContentListItem::Guides, "unedited-section"),
+                (ContentListItem::Explanation, "unedited-section"),
+                (ContentListItem::Reference, "unedited-section"),
+            ]
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
     style::{Color, Modifier, Style},
     widgets::{Block, Borders, Clear, Padding, Paragraph},
     Frame,
@@ -32,6 +32,7 @@ impl PopupButton {
 pub struct Popup {
     selected_button_index: usize,
     buttons: Vec<PopupButton>,
+    button_areas: Vec<Rect>,
 }
 
 impl Default for Popup {
@@ -49,6 +50,7 @@ impl Popup {
                 PopupButton::ExitWithoutSaving,
                 PopupButton::ExitWithSave,
             ],
+            button_areas: Vec::new(),
         }
     }
 
@@ -71,6 +73,15 @@ impl Popup {
             _ => unreachable!(),
         }
     }
+    pub fn click_button(&mut self, column: u16, row: u16) -> Option<PopupButton> {
+        let index = self
+            .button_areas
+            .iter()
+            .position(|area| area.contains(Position::new(column, row)))?;
+        self.selected_button_index = index;
+        Some(self.select_button())
+    }
+
     pub fn show_popup(&mut self, frame: &mut Frame, area: Rect) {
         let vertical = Layout::vertical([Constraint::Percentage(30)]).flex(Flex::Center);
         let horizontal = Layout::horizontal([Constraint::Percentage(80)]).flex(Flex::Center);
@@ -110,6 +121,7 @@ impl Popup {
                 .as_ref(),
             )
             .split(inner_chunks[1]);
+        self.button_areas = button_spaces.to_vec();
 
         for (i, &button) in self.buttons.iter().enumerate() {
             let button_style = if i == self.selected_button_index {
@@ -167,6 +179,22 @@ mod tests {
         assert_eq!(popup.select_button(), PopupButton::Cancel);
     }
 
+    #[test]
+    fn test_click_button() {
+        let mut popup = Popup::new();
+        popup.button_areas = vec![
+            Rect::new(0, 10, 10, 3),
+            Rect::new(10, 10, 10, 3),
+            Rect::new(20, 10, 10, 3),
+        ];
+
+        assert_eq!(popup.click_button(25, 11), Some(PopupButton::ExitWithSave));
+        assert_eq!(popup.select_button(), PopupButton::ExitWithSave);
+
+        assert_eq!(popup.click_button(5, 5), None);
+        assert_eq!(popup.select_button(), PopupButton::ExitWithSave);
+    }
+
     #[test]
     fn test_popup_button_labels() {
         let cancel = PopupButton::Cancel;
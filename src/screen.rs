@@ -1,18 +1,78 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
-    style::{Color, Style, Stylize},
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style, Stylize},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
+use std::{collections::HashSet, path::PathBuf};
 
-use crate::content::Content;
+use crate::content::{Content, ContentListItem};
+use crate::diagnostics;
+use crate::document::RenderOptions;
+use crate::front_matter::FrontMatterForm;
+use crate::keymap::{KeyContext, Keymap};
+use crate::lint::LintRules;
+use crate::markdown::MarkdownStyle;
+use crate::outline;
+use crate::packs::PackPicker;
 use crate::popup::Popup;
+use crate::search::{Search, SearchMode};
+use crate::sections::SectionDefinitions;
+use crate::snippets::SnippetPicker;
+use crate::spell::SpellChecker;
+use crate::stats::{self, Stats, StatsTargets};
+use crate::theme::Theme;
+use crate::vim::Vim;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NavigationRow {
+    Section(usize),
+    Heading {
+        section: usize,
+        line: usize,
+        depth: usize,
+        title: String,
+    },
+}
+
+impl NavigationRow {
+    fn section(&self) -> usize {
+        match self {
+            NavigationRow::Section(section) => *section,
+            NavigationRow::Heading { section, .. } => *section,
+        }
+    }
+}
 
 pub struct Screen {
     items: Vec<String>,
+    rows: Vec<NavigationRow>,
+    expanded: HashSet<usize>,
+    sections_with_headings: HashSet<usize>,
+    rows_source: Option<Vec<(ContentListItem, Vec<String>)>>,
+    document_sections: Vec<(ContentListItem, Vec<String>)>,
+    section_stats: Vec<(ContentListItem, Stats)>,
     list_state: ListState,
+    list_area: Rect,
     pub show_popup: bool,
+    pub show_diagnostics: bool,
+    pub show_outline: bool,
+    pub show_stats: bool,
     pub enable_insert_mode: bool,
+    pub status_message: Option<String>,
+    pub output_dir: PathBuf,
+    pub markdown_style: MarkdownStyle,
+    pub render_options: RenderOptions,
+    pub stats_targets: StatsTargets,
+    pub lint_rules: LintRules,
+    pub theme: Theme,
+    pub pack_picker: PackPicker,
+    pub snippet_picker: SnippetPicker,
+    pub front_matter_form: FrontMatterForm,
+    pub renaming: Option<String>,
+    navigation_hint: String,
+    editor_hint: String,
+    popup_hint: String,
 }
 
 impl Default for Screen {
@@ -25,20 +85,67 @@ impl Screen {
     pub fn new() -> Self {
         let mut list_state = ListState::default();
         list_state.select(Some(0));
+        let keymap = Keymap::default();
+        let items = Screen::section_labels(&SectionDefinitions::default());
         Self {
-            items: vec![
-                "Project Name".to_string(),
-                "Tutorials".to_string(),
-                "How-to Guides".to_string(),
-                "Explanation".to_string(),
-                "Reference".to_string(),
-            ],
+            rows: (0..items.len()).map(NavigationRow::Section).collect(),
+            items,
+            expanded: HashSet::new(),
+            sections_with_headings: HashSet::new(),
+            rows_source: None,
+            document_sections: Vec::new(),
+            section_stats: Vec::new(),
             list_state,
+            list_area: Rect::default(),
             show_popup: false,
+            show_diagnostics: false,
+            show_outline: false,
+            show_stats: false,
             enable_insert_mode: false,
+            status_message: None,
+            output_dir: PathBuf::new(),
+            markdown_style: MarkdownStyle::default(),
+            render_options: RenderOptions::default(),
+            stats_targets: StatsTargets::default(),
+            lint_rules: LintRules::default(),
+            theme: Theme::default(),
+            pack_picker: PackPicker::new(),
+            snippet_picker: SnippetPicker::new(),
+            front_matter_form: FrontMatterForm::new(),
+            renaming: None,
+            navigation_hint: keymap.hint(KeyContext::Navigation),
+            editor_hint: keymap.hint(KeyContext::Editor),
+            popup_hint: keymap.hint(KeyContext::Popup),
         }
     }
-    pub fn get_layout(&mut self, frame: &mut Frame, popup: &mut Popup, content: &mut Content) {
+
+    fn section_labels(definitions: &SectionDefinitions) -> Vec<String> {
+        Content::sections()
+            .iter()
+            .map(|section| definitions.label(section).to_string())
+            .collect()
+    }
+
+    pub fn set_render_options(&mut self, render_options: RenderOptions) {
+        self.items = Screen::section_labels(&render_options.sections);
+        self.render_options = render_options;
+        self.rows_source = None;
+    }
+
+    pub fn set_key_hints(&mut self, keymap: &Keymap) {
+        self.navigation_hint = keymap.hint(KeyContext::Navigation);
+        self.editor_hint = keymap.hint(KeyContext::Editor);
+        self.popup_hint = keymap.hint(KeyContext::Popup);
+    }
+    pub fn get_layout(
+        &mut self,
+        frame: &mut Frame,
+        popup: &mut Popup,
+        content: &mut Content,
+        search: &mut Search,
+        vim: &Vim,
+        spell: &mut SpellChecker,
+    ) {
         let area = frame.area();
         let all = Layout::default()
             .direction(Direction::Vertical)
@@ -46,52 +153,224 @@ impl Screen {
             .split(area);
         let top_area = all[0];
         let navbar = Block::new().title("CLI DOCS ".bold());
-        let additional_instruction = if content.enable_insert_mode {
-            "                      Use ◄ ▲ ▼ ► to navigate, F1 - page up, F2 - page down, press ESC to exit"
+        let additional_instruction = if let Some(message) = &self.status_message {
+            message.clone()
+        } else if self.show_popup {
+            self.popup_hint.clone()
+        } else if vim.enabled {
+            vim.status()
+        } else if content.enable_insert_mode {
+            self.editor_hint.clone()
         } else {
-            "                      Use ▲ ▼  to navigate, press i to edit, press ESC to exit"
+            self.navigation_hint.clone()
         };
-        frame.render_widget(navbar.title(additional_instruction), top_area);
+        frame.render_widget(
+            navbar.title(format!("                      {}", additional_instruction)),
+            top_area,
+        );
         let main_area = all[1];
         let layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(20), Constraint::Percentage(80)])
             .split(main_area);
         let navigation_menu = layout[0];
-        let content_area = layout[1];
+        let mut content_area = layout[1];
 
         let content_text = content.content_input.join("\n");
         let content_block = Paragraph::new(content_text.as_str()).block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red)),
+                .border_style(Style::default().fg(self.theme.border)),
         );
         frame.render_widget(content_block, content_area);
 
+        if self.rows_source.as_ref() != Some(&content.edited_sections()) {
+            self.refresh_rows(content);
+        }
         let items: Vec<ListItem> = self
-            .items
+            .rows
             .iter()
-            .map(|i| ListItem::new(i.as_str()))
+            .map(|row| match row {
+                NavigationRow::Section(section) => {
+                    let marker = if !self.sections_with_headings.contains(section) {
+                        "  "
+                    } else if self.expanded.contains(section) {
+                        "▾ "
+                    } else {
+                        "▸ "
+                    };
+                    match &self.renaming {
+                        Some(name) if self.selected_section() == Some(*section) => {
+                            ListItem::new(format!("{}{}_", marker, name))
+                                .style(Style::default().add_modifier(Modifier::UNDERLINED))
+                        }
+                        _ => ListItem::new(format!("{}{}", marker, self.items[*section])),
+                    }
+                }
+                NavigationRow::Heading { depth, title, .. } => {
+                    ListItem::new(format!("{}{}", "  ".repeat(depth + 2), title))
+                        .style(Style::default().fg(Color::Gray))
+                }
+            })
             .collect();
         let list = List::new(items)
             .block(Block::bordered())
             .style(Style::default().fg(Color::White))
-            .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black))
+            .highlight_style(Style::default().bg(self.theme.highlight).fg(Color::Black))
             .highlight_symbol(">> ");
 
+        self.list_area = navigation_menu;
         frame.render_stateful_widget(list, navigation_menu, &mut self.list_state);
+        if self.show_outline {
+            let [editor_area, outline_area] =
+                Layout::horizontal([Constraint::Min(0), Constraint::Percentage(35)])
+                    .areas(content_area);
+            let entries = outline::outline(&self.document_sections, &self.render_options);
+            outline::render_preview(frame, outline_area, &entries);
+            content_area = editor_area;
+        }
+        if self.show_stats {
+            let [editor_area, stats_area] = Layout::vertical([
+                Constraint::Min(0),
+                Constraint::Length(self.section_stats.len() as u16 + 4),
+            ])
+            .areas(content_area);
+            stats::render_panel(
+                frame,
+                stats_area,
+                &self.section_stats,
+                &self.stats_targets,
+                &self.render_options.sections,
+            );
+            content_area = editor_area;
+        }
+        let mut editor_highlights = vim.highlights(content);
+        editor_highlights.extend(spell.highlights(&content.content_input));
+        editor_highlights.extend(
+            content.tab_stop_highlights(
+                Style::default()
+                    .fg(self.theme.highlight)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        );
         if self.show_popup {
             popup.show_popup(frame, area);
+        } else if search.active {
+            let [editor_area, search_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(search.height())])
+                    .areas(content_area);
+            let mut highlights = content
+                .current_section()
+                .map(|section| search.highlights(&section))
+                .unwrap_or_default();
+            highlights.extend(editor_highlights);
+            content.render(frame, editor_area, self.selected_section(), &highlights);
+            if search.mode == SearchMode::Project && !search.confirming {
+                search.render_results(frame, editor_area);
+            }
+            search.render(frame, search_area);
+        } else if self.show_diagnostics {
+            let diagnostics = diagnostics::collect(
+                content,
+                &self.output_dir,
+                &self.markdown_style,
+                &self.stats_targets,
+                &self.lint_rules,
+            );
+            let panel_height = (diagnostics.len() as u16 + 2).clamp(3, 10);
+            let [editor_area, panel_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(panel_height)])
+                    .areas(content_area);
+            let mut highlights = content
+                .current_section()
+                .map(|section| diagnostics::highlights(&diagnostics, &section))
+                .unwrap_or_default();
+            highlights.extend(editor_highlights);
+            content.render(frame, editor_area, self.selected_section(), &highlights);
+            diagnostics::render_panel(frame, panel_area, &diagnostics);
         } else {
-            content.render(frame, content_area, &self.list_state);
-            if content.enable_insert_mode {}
+            content.render(
+                frame,
+                content_area,
+                self.selected_section(),
+                &editor_highlights,
+            );
+        }
+        if spell.suggestions.active && !self.show_popup {
+            spell.suggestions.render(frame, content_area);
+        }
+        if self.snippet_picker.active && !self.show_popup {
+            self.snippet_picker.render(frame, content_area);
+        }
+        if self.front_matter_form.active {
+            self.front_matter_form.render(frame, area);
+        }
+        if self.pack_picker.active {
+            self.pack_picker.render(frame, area);
+        }
+    }
+
+    fn refresh_rows(&mut self, content: &Content) {
+        self.rows_source = Some(content.edited_sections());
+        self.document_sections = content.document_sections();
+        self.section_stats = self
+            .document_sections
+            .iter()
+            .map(|(section, lines)| (section.clone(), Stats::of(lines)))
+            .collect();
+        let entries = outline::outline(&self.document_sections, &self.render_options);
+        self.sections_with_headings.clear();
+        self.rows.clear();
+        let order: Vec<usize> = self
+            .render_options
+            .sections
+            .order()
+            .iter()
+            .map(Content::index_for_section)
+            .collect();
+        for section in order {
+            self.rows.push(NavigationRow::Section(section));
+            let headings = entries.iter().filter(|entry| {
+                entry.line.is_some() && Content::index_for_section(&entry.section) == section
+            });
+            for entry in headings {
+                self.sections_with_headings.insert(section);
+                if self.expanded.contains(&section) {
+                    self.rows.push(NavigationRow::Heading {
+                        section,
+                        line: entry.line.unwrap_or_default(),
+                        depth: entry.level.saturating_sub(3),
+                        title: entry.title.clone(),
+                    });
+                }
+            }
+        }
+        if self
+            .list_state
+            .selected()
+            .is_some_and(|row| row >= self.rows.len())
+        {
+            self.list_state.select(Some(self.rows.len() - 1));
+        }
+    }
+
+    pub fn selected_section(&self) -> Option<usize> {
+        let row = self.list_state.selected()?;
+        self.rows.get(row).map(NavigationRow::section)
+    }
+
+    pub fn selected_heading_line(&self) -> Option<usize> {
+        match self.rows.get(self.list_state.selected()?)? {
+            NavigationRow::Heading { line, .. } => Some(*line),
+            NavigationRow::Section(_) => None,
         }
     }
 
     pub fn next(&mut self, content: &mut Content) {
+        self.refresh_rows(content);
         let i = match self.list_state.selected() {
             Some(i) => {
-                if i >= self.items.len() - 1 {
+                if i >= self.rows.len() - 1 {
                     0
                 } else {
                     i + 1
@@ -99,31 +378,89 @@ impl Screen {
             }
             None => 0,
         };
-        self.list_state.select(Some(i));
-        content.select_placeholder(i)
+        self.select_row(i, content)
     }
     pub fn previous(&mut self, content: &mut Content) {
+        self.refresh_rows(content);
         let i = match self.list_state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.items.len() - 1
+                    self.rows.len() - 1
                 } else {
                     i - 1
                 }
             }
             None => 0,
         };
-        self.list_state.select(Some(i));
-        content.select_placeholder(i)
+        self.select_row(i, content)
     }
+    pub fn select(&mut self, index: usize, content: &mut Content) {
+        self.refresh_rows(content);
+        if let Some(row) = self
+            .rows
+            .iter()
+            .position(|row| row == &NavigationRow::Section(index))
+        {
+            self.select_row(row, content)
+        }
+    }
+
+    pub fn select_row(&mut self, row: usize, content: &mut Content) {
+        let Some(selected) = self.rows.get(row) else {
+            return;
+        };
+        self.list_state.select(Some(row));
+        content.select_placeholder(selected.section());
+        if let NavigationRow::Heading { line, .. } = selected {
+            content.jump_to_line(*line);
+        }
+    }
+
+    pub fn expand(&mut self, content: &mut Content) {
+        if let Some(section) = self.selected_section() {
+            self.expanded.insert(section);
+            self.refresh_rows(content);
+        }
+    }
+
+    pub fn collapse(&mut self, content: &mut Content) {
+        if let Some(section) = self.selected_section() {
+            self.expanded.remove(&section);
+            self.refresh_rows(content);
+            self.select(section, content);
+        }
+    }
+
+    pub fn item_at(&self, column: u16, row: u16) -> Option<usize> {
+        let inner_area = Block::bordered().inner(self.list_area);
+        if !inner_area.contains(Position::new(column, row)) {
+            return None;
+        }
+        let index = self.list_state.offset() + (row - inner_area.y) as usize;
+        (index < self.rows.len()).then_some(index)
+    }
+
     pub fn toggle_popup(&mut self) {
         self.show_popup = !self.show_popup;
     }
+
+    pub fn toggle_diagnostics(&mut self) {
+        self.show_diagnostics = !self.show_diagnostics;
+    }
+
+    pub fn toggle_outline(&mut self) {
+        self.show_outline = !self.show_outline;
+    }
+
+    pub fn toggle_stats(&mut self) {
+        self.show_stats = !self.show_stats;
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::content::Content;
+    use crate::content::{Content, ContentListItem};
+    use ratatui::layout::Rect;
 
     use super::Screen;
 
@@ -193,6 +530,59 @@ mod tests {
         assert_eq!(screen.items[4], "Reference");
     }
 
+    #[test]
+    fn test_item_at_maps_rows_to_items() {
+        let mut screen = Screen::new();
+        screen.list_area = Rect::new(0, 2, 20, 10);
+
+        assert_eq!(screen.item_at(5, 2), None);
+        assert_eq!(screen.item_at(5, 3), Some(0));
+        assert_eq!(screen.item_at(5, 7), Some(4));
+        assert_eq!(screen.item_at(5, 8), None);
+        assert_eq!(screen.item_at(30, 3), None);
+    }
+
+    #[test]
+    fn test_select_ignores_out_of_range_index() {
+        let mut screen = Screen::new();
+        let mut content = Content::new();
+        screen.select(3, &mut content);
+        assert_eq!(screen.list_state.selected(), Some(3));
+
+        screen.select(10, &mut content);
+        assert_eq!(screen.list_state.selected(), Some(3));
+    }
+
+    #[test]
+    fn test_expanded_section_lists_headings() {
+        let mut screen = Screen::new();
+        let mut content = Content::new();
+        content.file_to_save.insert(
+            ContentListItem::Tutorials,
+            vec![
+                "### Setup".to_string(),
+                "text".to_string(),
+                "#### Run".to_string(),
+            ],
+        );
+        screen.select(1, &mut content);
+        screen.expand(&mut content);
+        assert_eq!(screen.rows.len(), 7);
+
+        screen.next(&mut content);
+        screen.next(&mut content);
+        assert_eq!(screen.selected_section(), Some(1));
+        assert_eq!(screen.selected_heading_line(), Some(2));
+        assert_eq!(content.cursor_index_y, 2);
+
+        screen.collapse(&mut content);
+        assert_eq!(screen.rows.len(), 5);
+        assert_eq!(screen.list_state.selected(), Some(1));
+
+        screen.next(&mut content);
+        assert_eq!(screen.selected_section(), Some(2));
+    }
+
     #[test]
     fn test_selection_persistence_after_toggle_popup() {
         let mut screen = Screen::new();
@@ -0,0 +1,426 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use regex::Regex;
+
+use crate::content::{ContentListItem, Highlight};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    Find,
+    Project,
+    Replace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Query,
+    Replacement,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub section: ContentListItem,
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+    pub replacement: String,
+}
+
+pub struct Search {
+    pub active: bool,
+    pub mode: SearchMode,
+    pub field: SearchField,
+    pub query: String,
+    pub replacement: String,
+    pub use_regex: bool,
+    pub all_sections: bool,
+    pub confirming: bool,
+    pub matches: Vec<SearchMatch>,
+    pub current: usize,
+    pub error: Option<String>,
+    pub replaced_count: usize,
+    results_state: ListState,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Search {
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            mode: SearchMode::Find,
+            field: SearchField::Query,
+            query: String::new(),
+            replacement: String::new(),
+            use_regex: false,
+            all_sections: false,
+            confirming: false,
+            matches: Vec::new(),
+            current: 0,
+            error: None,
+            replaced_count: 0,
+            results_state: ListState::default(),
+        }
+    }
+
+    pub fn open(&mut self, mode: SearchMode) {
+        self.active = true;
+        self.mode = mode;
+        self.field = SearchField::Query;
+        self.all_sections = mode == SearchMode::Project;
+        self.confirming = false;
+        self.matches.clear();
+        self.current = 0;
+        self.error = None;
+        self.replaced_count = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.confirming = false;
+        self.matches.clear();
+        self.error = None;
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        match self.field {
+            SearchField::Query => self.query.push(c),
+            SearchField::Replacement => self.replacement.push(c),
+        }
+    }
+
+    pub fn delete_char(&mut self) {
+        match self.field {
+            SearchField::Query => self.query.pop(),
+            SearchField::Replacement => self.replacement.pop(),
+        };
+    }
+
+    pub fn toggle_field(&mut self) {
+        if self.mode == SearchMode::Replace {
+            self.field = match self.field {
+                SearchField::Query => SearchField::Replacement,
+                SearchField::Replacement => SearchField::Query,
+            };
+        }
+    }
+
+    pub fn toggle_regex(&mut self) {
+        self.use_regex = !self.use_regex;
+    }
+
+    pub fn toggle_scope(&mut self) {
+        if self.mode == SearchMode::Replace {
+            self.all_sections = !self.all_sections;
+        }
+    }
+
+    fn pattern(&self) -> Result<Regex, regex::Error> {
+        if self.use_regex {
+            Regex::new(&self.query)
+        } else {
+            Regex::new(&regex::escape(&self.query))
+        }
+    }
+
+    pub fn find_in_lines(
+        &self,
+        section: &ContentListItem,
+        lines: &[String],
+    ) -> Result<Vec<SearchMatch>, regex::Error> {
+        if self.query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pattern = self.pattern()?;
+        let mut matches = Vec::new();
+        for (line_index, line) in lines.iter().enumerate() {
+            for captures in pattern.captures_iter(line) {
+                let found = captures.get(0).expect("capture 0 is always present");
+                if found.is_empty() {
+                    continue;
+                }
+                let mut replacement = String::new();
+                if self.use_regex {
+                    captures.expand(&self.replacement, &mut replacement);
+                } else {
+                    replacement.push_str(&self.replacement);
+                }
+                matches.push(SearchMatch {
+                    section: section.clone(),
+                    line: line_index,
+                    start: found.start(),
+                    end: found.end(),
+                    text: line.clone(),
+                    replacement,
+                });
+            }
+        }
+        Ok(matches)
+    }
+
+    pub fn set_matches(&mut self, matches: Vec<SearchMatch>, line: usize, column: usize) {
+        self.current = matches
+            .iter()
+            .position(|found| (found.line, found.start) >= (line, column))
+            .unwrap_or(0);
+        self.matches = matches;
+        self.error = None;
+    }
+
+    pub fn set_error(&mut self, error: regex::Error) {
+        self.matches.clear();
+        self.current = 0;
+        self.error = Some(error.to_string());
+    }
+
+    pub fn current_match(&self) -> Option<&SearchMatch> {
+        self.matches.get(self.current)
+    }
+
+    pub fn next(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + 1) % self.matches.len();
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = self
+                .current
+                .checked_sub(1)
+                .unwrap_or(self.matches.len() - 1);
+        }
+    }
+
+    pub fn skip_current(&mut self) {
+        self.current += 1;
+    }
+
+    pub fn complete_replacement(&mut self) {
+        let Some(replaced) = self.matches.get(self.current).cloned() else {
+            return;
+        };
+        let delta = replaced.replacement.len() as isize - (replaced.end - replaced.start) as isize;
+        for found in self.matches.iter_mut().skip(self.current + 1) {
+            if found.section == replaced.section && found.line == replaced.line {
+                found.start = found.start.saturating_add_signed(delta);
+                found.end = found.end.saturating_add_signed(delta);
+            }
+        }
+        self.replaced_count += 1;
+        self.current += 1;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.matches.len()
+    }
+
+    pub fn highlights(&self, section: &ContentListItem) -> Vec<Highlight> {
+        if !self.active {
+            return Vec::new();
+        }
+        self.matches
+            .iter()
+            .enumerate()
+            .filter(|(_, found)| &found.section == section)
+            .map(|(index, found)| Highlight {
+                line: found.line,
+                start: found.start,
+                end: found.end,
+                style: if index == self.current {
+                    Style::default().bg(Color::LightRed).fg(Color::Black)
+                } else {
+                    Style::default().bg(Color::Yellow).fg(Color::Black)
+                },
+            })
+            .collect()
+    }
+
+    pub fn height(&self) -> u16 {
+        let replacement_line = self.mode == SearchMode::Replace && !self.confirming;
+        3 + replacement_line as u16 + self.error.is_some() as u16
+    }
+
+    fn title(&self) -> String {
+        let scope = if self.all_sections {
+            "all sections"
+        } else {
+            "section"
+        };
+        let kind = match self.mode {
+            SearchMode::Find | SearchMode::Project => "Find",
+            SearchMode::Replace => "Replace",
+        };
+        let regex = if self.use_regex { " [regex]" } else { "" };
+        let position = if self.matches.is_empty() {
+            "no matches".to_string()
+        } else {
+            format!(
+                "{}/{}",
+                (self.current + 1).min(self.matches.len()),
+                self.matches.len()
+            )
+        };
+        format!(" {} in {}{} - {} ", kind, scope, regex, position)
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let mut lines = Vec::new();
+        if self.confirming {
+            lines.push(Line::from(format!(
+                "Replace with \"{}\"? (y)es (n)o (a)ll (q)uit",
+                self.current_match()
+                    .map(|found| found.replacement.as_str())
+                    .unwrap_or_default()
+            )));
+        } else {
+            let marker = |field| if self.field == field { "> " } else { "  " };
+            lines.push(Line::from(format!(
+                "{}Find: {}",
+                marker(SearchField::Query),
+                self.query
+            )));
+            if self.mode == SearchMode::Replace {
+                lines.push(Line::from(format!(
+                    "{}With: {}",
+                    marker(SearchField::Replacement),
+                    self.replacement
+                )));
+            }
+        }
+        if let Some(error) = &self.error {
+            lines.push(Line::styled(
+                error.lines().last().unwrap_or_default().to_string(),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue))
+            .title(self.title());
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    pub fn render_results(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|found| {
+                ListItem::new(format!(
+                    "{:?}:{}  {}",
+                    found.section,
+                    found.line + 1,
+                    found.text.trim()
+                ))
+            })
+            .collect();
+        self.results_state
+            .select((!self.matches.is_empty()).then_some(self.current));
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Blue))
+                    .title(" Results "),
+            )
+            .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black));
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut self.results_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+
+    #[test]
+    fn test_plain_query_escapes_regex_characters() {
+        let mut search = Search::new();
+        search.open(SearchMode::Find);
+        search.query = "a.b".to_string();
+
+        let matches = search
+            .find_in_lines(&ContentListItem::Guides, &lines(&["a.b axb a.b"]))
+            .unwrap();
+        let positions: Vec<(usize, usize)> = matches.iter().map(|m| (m.start, m.end)).collect();
+        assert_eq!(positions, vec![(0, 3), (8, 11)]);
+    }
+
+    #[test]
+    fn test_regex_replacement_expands_captures() {
+        let mut search = Search::new();
+        search.open(SearchMode::Replace);
+        search.use_regex = true;
+        search.query = r"Step (\d)".to_string();
+        search.replacement = "Stage $1".to_string();
+
+        let matches = search
+            .find_in_lines(&ContentListItem::Tutorials, &lines(&["Step 1", "Step 2"]))
+            .unwrap();
+        assert_eq!(matches[0].replacement, "Stage 1");
+        assert_eq!(matches[1].replacement, "Stage 2");
+    }
+
+    #[test]
+    fn test_invalid_regex_is_reported() {
+        let mut search = Search::new();
+        search.open(SearchMode::Find);
+        search.use_regex = true;
+        search.query = "(".to_string();
+
+        let result = search.find_in_lines(&ContentListItem::Reference, &lines(&["("]));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_set_matches_selects_first_match_after_cursor() {
+        let mut search = Search::new();
+        search.open(SearchMode::Find);
+        search.query = "x".to_string();
+        let matches = search
+            .find_in_lines(&ContentListItem::Guides, &lines(&["x", "x x"]))
+            .unwrap();
+
+        search.set_matches(matches, 1, 1);
+        assert_eq!(search.current, 2);
+
+        search.next();
+        assert_eq!(search.current, 0);
+        search.previous();
+        assert_eq!(search.current, 2);
+    }
+
+    #[test]
+    fn test_complete_replacement_shifts_matches_on_same_line() {
+        let mut search = Search::new();
+        search.open(SearchMode::Replace);
+        search.query = "ab".to_string();
+        search.replacement = "abcd".to_string();
+        let matches = search
+            .find_in_lines(&ContentListItem::Guides, &lines(&["ab ab", "ab"]))
+            .unwrap();
+        search.set_matches(matches, 0, 0);
+
+        search.complete_replacement();
+        assert_eq!(search.current, 1);
+        assert_eq!((search.matches[1].start, search.matches[1].end), (5, 7));
+        assert_eq!((search.matches[2].start, search.matches[2].end), (0, 2));
+        assert_eq!(search.replaced_count, 1);
+
+        search.skip_current();
+        search.skip_current();
+        assert!(search.is_finished());
+    }
+}
@@ -0,0 +1,368 @@
+use color_eyre::eyre::{eyre, Result};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+use crate::config::{LoadedConfig, PROJECT_CONFIG_FILE};
+use crate::content::ContentListItem;
+use crate::metadata::{self, ProjectMetadata};
+
+pub const DEFAULT_TEMPLATE_DIR: &str = "templates";
+pub const SECTION_KEYS: [(&str, ContentListItem); 5] = [
+    ("project", ContentListItem::ProjectName),
+    ("tutorials", ContentListItem::Tutorials),
+    ("guides", ContentListItem::Guides),
+    ("explanation", ContentListItem::Explanation),
+    ("reference", ContentListItem::Reference),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionDefinition {
+    pub heading: String,
+    pub label: String,
+    pub template: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionDefinitions {
+    pub template_dir: PathBuf,
+    pub project_dir: PathBuf,
+    pub metadata: ProjectMetadata,
+    pub variables: BTreeMap<String, String>,
+    order: Vec<ContentListItem>,
+    definitions: HashMap<ContentListItem, SectionDefinition>,
+}
+
+impl Default for SectionDefinitions {
+    fn default() -> Self {
+        let definition = |heading: &str, label: &str, template: &str| SectionDefinition {
+            heading: heading.to_string(),
+            label: label.to_string(),
+            template: template.to_string(),
+        };
+        Self {
+            template_dir: PathBuf::from(DEFAULT_TEMPLATE_DIR),
+            project_dir: PathBuf::from("."),
+            metadata: ProjectMetadata::default(),
+            variables: BTreeMap::new(),
+            order: SECTION_KEYS.map(|(_, section)| section).to_vec(),
+            definitions: HashMap::from([
+                (
+                    ContentListItem::ProjectName,
+                    definition("ProjectName", "Project Name", "name.md"),
+                ),
+                (
+                    ContentListItem::Tutorials,
+                    definition("Tutorials", "Tutorials", "tutorials.md"),
+                ),
+                (
+                    ContentListItem::Guides,
+                    definition("Guides", "How-to Guides", "guides.md"),
+                ),
+                (
+                    ContentListItem::Explanation,
+                    definition("Explanation", "Explanation", "explanation.md"),
+                ),
+                (
+                    ContentListItem::Reference,
+                    definition("Reference", "Reference", "reference.md"),
+                ),
+            ]),
+        }
+    }
+}
+
+pub fn section_key(section: &ContentListItem) -> &'static str {
+    SECTION_KEYS
+        .iter()
+        .find(|(_, item)| item == section)
+        .map_or("project", |(key, _)| key)
+}
+
+pub fn section_for_name(key: &str) -> Option<&'static ContentListItem> {
+    SECTION_KEYS
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, section)| section)
+}
+
+pub fn section_for_key(
+    config: &LoadedConfig,
+    table: &str,
+    key: &str,
+) -> Result<&'static ContentListItem> {
+    section_for_name(key).ok_or_else(|| {
+        eyre!(
+            "{}: unknown section '{}' in [{}] (available: {})",
+            config.path.display(),
+            key,
+            table,
+            SECTION_KEYS.map(|(name, _)| name).join(", ")
+        )
+    })
+}
+
+impl SectionDefinitions {
+    pub fn from_configs(configs: &[LoadedConfig], template_dir: Option<PathBuf>) -> Result<Self> {
+        let mut definitions = SectionDefinitions::default();
+        let mut heading_sources: HashMap<&ContentListItem, &Path> = HashMap::new();
+        for config in configs {
+            if config.path.file_name() == Some(OsStr::new(PROJECT_CONFIG_FILE)) {
+                definitions.project_dir = config
+                    .path
+                    .parent()
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+            }
+            if let Some(dir) = &config.file.templates.dir {
+                definitions.template_dir = config.resolve_path(dir);
+            }
+            definitions
+                .variables
+                .extend(config.file.templates.variables.clone());
+            for (key, section_config) in &config.file.sections {
+                let section = section_for_key(config, "sections", key)?;
+                let definition = definitions
+                    .definitions
+                    .get_mut(section)
+                    .expect("every section has a definition");
+                if let Some(heading) = &section_config.heading {
+                    if heading.trim().is_empty() {
+                        return Err(eyre!(
+                            "{}: heading for [sections.{}] cannot be empty",
+                            config.path.display(),
+                            key
+                        ));
+                    }
+                    definition.heading = heading.trim().to_string();
+                    heading_sources.insert(section, &config.path);
+                }
+                if let Some(label) = &section_config.label {
+                    definition.label = label.clone();
+                }
+                if let Some(template) = &section_config.template {
+                    definition.template = template.clone();
+                }
+            }
+            if let Some(order) = &config.file.document.order {
+                definitions.order = vec![ContentListItem::ProjectName];
+                for key in order {
+                    let section = section_for_key(config, "document", key)?;
+                    if section == &ContentListItem::ProjectName {
+                        return Err(eyre!(
+                            "{}: '{}' cannot be ordered in [document] order, the project name always comes first",
+                            config.path.display(),
+                            key
+                        ));
+                    }
+                    if definitions.order.contains(section) {
+                        return Err(eyre!(
+                            "{}: section '{}' is listed twice in [document] order",
+                            config.path.display(),
+                            key
+                        ));
+                    }
+                    definitions.order.push(section.clone());
+                }
+                for (_, section) in &SECTION_KEYS {
+                    if !definitions.order.contains(section) {
+                        definitions.order.push(section.clone());
+                    }
+                }
+            }
+        }
+        for (key, section) in &SECTION_KEYS {
+            let heading = definitions.heading(section);
+            if let Some(other) = definitions
+                .section_for_heading(heading)
+                .filter(|other| other != section)
+            {
+                return Err(eyre!(
+                    "{}: [sections.{}] and [sections.{}] both use the heading '{}'",
+                    heading_sources
+                        .get(section)
+                        .or_else(|| heading_sources.get(&other))
+                        .map_or_else(String::new, |path| path.display().to_string()),
+                    section_key(&other),
+                    key,
+                    heading
+                ));
+            }
+        }
+        if let Some(dir) = template_dir {
+            definitions.template_dir = dir;
+        }
+        definitions.metadata = metadata::collect(&definitions.project_dir);
+        Ok(definitions)
+    }
+
+    pub fn get(&self, section: &ContentListItem) -> &SectionDefinition {
+        self.definitions
+            .get(section)
+            .expect("every section has a definition")
+    }
+
+    pub fn heading(&self, section: &ContentListItem) -> &str {
+        &self.get(section).heading
+    }
+
+    pub fn label(&self, section: &ContentListItem) -> &str {
+        &self.get(section).label
+    }
+
+    pub fn order(&self) -> &[ContentListItem] {
+        &self.order
+    }
+
+    pub fn order_keys(&self) -> Vec<&'static str> {
+        self.order
+            .iter()
+            .filter(|section| **section != ContentListItem::ProjectName)
+            .map(section_key)
+            .collect()
+    }
+
+    pub fn move_section(&mut self, section: &ContentListItem, up: bool) -> bool {
+        let Some(index) = self.order.iter().position(|item| item == section) else {
+            return false;
+        };
+        let target = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        };
+        match target {
+            Some(target) if target > 0 && index > 0 && target < self.order.len() => {
+                self.order.swap(index, target);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn rename(&mut self, section: &ContentListItem, name: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(eyre!("Section name cannot be empty"));
+        }
+        if let Some(other) = self
+            .section_for_heading(name)
+            .filter(|other| other != section)
+        {
+            return Err(eyre!(
+                "'{}' is already the heading of {}",
+                name,
+                self.label(&other)
+            ));
+        }
+        let definition = self
+            .definitions
+            .get_mut(section)
+            .expect("every section has a definition");
+        definition.heading = name.to_string();
+        definition.label = name.to_string();
+        Ok(())
+    }
+
+    pub fn template_path(&self, section: &ContentListItem) -> PathBuf {
+        self.template_dir.join(&self.get(section).template)
+    }
+
+    pub fn section_for_heading(&self, heading: &str) -> Option<ContentListItem> {
+        SECTION_KEYS
+            .iter()
+            .map(|(_, section)| section)
+            .find(|section| self.heading(section) == heading.trim())
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFile;
+
+    fn config(toml: &str) -> LoadedConfig {
+        LoadedConfig {
+            path: PathBuf::from("project/.txtui.toml"),
+            file: toml::from_str::<ConfigFile>(toml).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_sections_are_configurable() {
+        let definitions = SectionDefinitions::from_configs(
+            &[config(
+                "[templates]\ndir = \"docs/templates\"\n[sections.guides]\nheading = \"How-To\"\ntemplate = \"howto.md\"",
+            )],
+            None,
+        )
+        .unwrap();
+        assert_eq!(definitions.heading(&ContentListItem::Guides), "How-To");
+        assert_eq!(definitions.label(&ContentListItem::Guides), "How-to Guides");
+        assert_eq!(
+            definitions.template_path(&ContentListItem::Guides),
+            PathBuf::from("project/docs/templates/howto.md")
+        );
+        assert_eq!(definitions.project_dir, PathBuf::from("project"));
+        assert_eq!(
+            definitions.section_for_heading("How-To"),
+            Some(ContentListItem::Guides)
+        );
+        assert_eq!(definitions.section_for_heading("Guides"), None);
+
+        assert!(SectionDefinitions::from_configs(
+            &[config("[sections.intro]\nlabel = \"x\"")],
+            None
+        )
+        .is_err());
+        assert!(SectionDefinitions::from_configs(
+            &[config("[sections.guides]\nheading = \"Reference\"")],
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_sections_are_reordered_and_renamed() {
+        let mut definitions = SectionDefinitions::from_configs(
+            &[config("[document]\norder = [\"reference\", \"tutorials\"]")],
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            definitions.order_keys(),
+            vec!["reference", "tutorials", "guides", "explanation"]
+        );
+        assert!(definitions.move_section(&ContentListItem::Tutorials, true));
+        assert!(!definitions.move_section(&ContentListItem::Tutorials, true));
+        assert!(!definitions.move_section(&ContentListItem::ProjectName, false));
+        assert!(!definitions.move_section(&ContentListItem::Explanation, false));
+        assert_eq!(
+            definitions.order_keys(),
+            vec!["tutorials", "reference", "guides", "explanation"]
+        );
+
+        definitions
+            .rename(&ContentListItem::Guides, " Recipes ")
+            .unwrap();
+        assert_eq!(definitions.label(&ContentListItem::Guides), "Recipes");
+        assert_eq!(
+            definitions.section_for_heading("Recipes"),
+            Some(ContentListItem::Guides)
+        );
+        assert!(definitions
+            .rename(&ContentListItem::Tutorials, "Recipes")
+            .is_err());
+
+        for order in ["[\"guides\", \"guides\"]", "[\"project\"]"] {
+            assert!(SectionDefinitions::from_configs(
+                &[config(&format!("[document]\norder = {}", order))],
+                None
+            )
+            .is_err());
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use color_eyre::eyre::{eyre, Result};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::config::LoadedConfig;
+use crate::content::{ContentListItem, TabStop};
+use crate::sections::section_for_name;
+
+static TAB_STOP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\$\{(\d+)(?::([^}]*))?\}|\$(\d+)").expect("valid tab-stop regex")
+});
+
+pub const BUILTIN_SNIPPETS: [(&str, &str, &[&str], &str); 5] = [
+    (
+        "prerequisites",
+        "List of what readers need before starting",
+        &["tutorials", "guides"],
+        "#### Prerequisites\n - ${1:Tool} ${2:version} or higher\n - ${3:Account or access needed}\n$0",
+    ),
+    (
+        "step",
+        "Numbered step with a command",
+        &["tutorials", "guides"],
+        "${1:1}. **${2:Step}**: ${3:Describe the action.}\n```sh\n${4:command}\n```\n$0",
+    ),
+    (
+        "admonition",
+        "Note, tip or warning the reader should not miss",
+        &[],
+        "> **${1:Note}**: ${2:Text the reader should not miss.}\n$0",
+    ),
+    (
+        "option-table",
+        "Table of options with defaults",
+        &["reference"],
+        "| Option | Default | Description |\n| --- | --- | --- |\n| `${1:--option}` | ${2:none} | ${3:What the option does.} |\n$0",
+    ),
+    (
+        "troubleshooting",
+        "Problem, symptom and solution",
+        &["guides", "reference"],
+        "#### ${1:Problem}\n**Symptom**: ${2:What the reader sees.}\n\n**Solution**: ${3:How to fix it.}\n$0",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub name: String,
+    pub description: String,
+    pub sections: Vec<ContentListItem>,
+    pub body: String,
+}
+
+impl Snippet {
+    pub fn applies_to(&self, section: &ContentListItem) -> bool {
+        self.sections.is_empty() || self.sections.contains(section)
+    }
+
+    pub fn expand(&self) -> (Vec<String>, Vec<TabStop>) {
+        let mut lines = Vec::new();
+        let mut stops: Vec<(usize, TabStop)> = Vec::new();
+        for (line, text) in self.body.lines().enumerate() {
+            let mut expanded = String::new();
+            let mut position = 0;
+            for captures in TAB_STOP.captures_iter(text) {
+                let found = captures.get(0).expect("whole match");
+                expanded.push_str(&text[position..found.start()]);
+                let number = captures
+                    .get(1)
+                    .or_else(|| captures.get(3))
+                    .and_then(|number| number.as_str().parse().ok())
+                    .unwrap_or(0);
+                let placeholder = captures
+                    .get(2)
+                    .map_or("", |placeholder| placeholder.as_str());
+                let start = expanded.len();
+                expanded.push_str(placeholder);
+                stops.push((
+                    number,
+                    TabStop {
+                        line,
+                        start,
+                        end: expanded.len(),
+                    },
+                ));
+                position = found.end();
+            }
+            expanded.push_str(&text[position..]);
+            lines.push(expanded);
+        }
+        stops.sort_by_key(|(number, stop)| (*number == 0, *number, stop.line, stop.start));
+        (lines, stops.into_iter().map(|(_, stop)| stop).collect())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetLibrary {
+    snippets: Vec<Snippet>,
+}
+
+impl Default for SnippetLibrary {
+    fn default() -> Self {
+        let snippets = BUILTIN_SNIPPETS
+            .iter()
+            .map(|(name, description, sections, body)| Snippet {
+                name: name.to_string(),
+                description: description.to_string(),
+                sections: sections
+                    .iter()
+                    .filter_map(|key| section_for_name(key).cloned())
+                    .collect(),
+                body: body.to_string(),
+            })
+            .collect();
+        Self { snippets }
+    }
+}
+
+impl SnippetLibrary {
+    pub fn from_configs(configs: &[LoadedConfig]) -> Result<Self> {
+        let mut library = SnippetLibrary::default();
+        for config in configs {
+            let mut names: Vec<&String> = config.file.snippets.keys().collect();
+            names.sort();
+            for name in names {
+                let snippet = &config.file.snippets[name];
+                let mut sections = Vec::new();
+                for key in snippet.sections.iter().flatten() {
+                    let section = section_for_name(key).ok_or_else(|| {
+                        eyre!(
+                            "{}: unknown section '{}' in [snippets.{}]",
+                            config.path.display(),
+                            key,
+                            name
+                        )
+                    })?;
+                    sections.push(section.clone());
+                }
+                let snippet = Snippet {
+                    name: name.clone(),
+                    description: snippet.description.clone().unwrap_or_default(),
+                    sections,
+                    body: snippet.body.clone(),
+                };
+                match library
+                    .snippets
+                    .iter_mut()
+                    .find(|existing| existing.name == snippet.name)
+                {
+                    Some(existing) => *existing = snippet,
+                    None => library.snippets.push(snippet),
+                }
+            }
+        }
+        Ok(library)
+    }
+
+    pub fn for_section(&self, section: &ContentListItem) -> Vec<Snippet> {
+        self.snippets
+            .iter()
+            .filter(|snippet| snippet.applies_to(section))
+            .cloned()
+            .collect()
+    }
+}
+
+fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    for wanted in query.to_lowercase().chars() {
+        let found = text[position..].iter().position(|c| *c == wanted)?;
+        score += found;
+        position += found + 1;
+    }
+    Some(score)
+}
+
+#[derive(Debug, Default)]
+pub struct SnippetPicker {
+    pub active: bool,
+    query: String,
+    snippets: Vec<Snippet>,
+    matches: Vec<usize>,
+    state: ListState,
+}
+
+impl SnippetPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, snippets: Vec<Snippet>) {
+        self.active = true;
+        self.query.clear();
+        self.snippets = snippets;
+        self.refresh();
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.query.clear();
+        self.snippets.clear();
+        self.matches.clear();
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.query.push(c);
+        self.refresh();
+    }
+
+    pub fn delete_char(&mut self) {
+        self.query.pop();
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        let mut scored: Vec<(usize, usize)> = self
+            .snippets
+            .iter()
+            .enumerate()
+            .filter_map(|(index, snippet)| {
+                let score = fuzzy_score(&self.query, &snippet.name).or_else(|| {
+                    fuzzy_score(&self.query, &snippet.description).map(|score| score + 100)
+                })?;
+                Some((score, index))
+            })
+            .collect();
+        scored.sort_by(|(a_score, a), (b_score, b)| {
+            a_score
+                .cmp(b_score)
+                .then_with(|| self.snippets[*a].name.cmp(&self.snippets[*b].name))
+        });
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.state.select((!self.matches.is_empty()).then_some(0));
+    }
+
+    pub fn next(&mut self) {
+        if let Some(selected) = self.state.selected() {
+            self.state.select(Some((selected + 1) % self.matches.len()));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if let Some(selected) = self.state.selected() {
+            self.state.select(Some(
+                (selected + self.matches.len() - 1) % self.matches.len(),
+            ));
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Snippet> {
+        let index = self.matches.get(self.state.selected()?)?;
+        self.snippets.get(*index)
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let mut items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|index| {
+                let snippet = &self.snippets[*index];
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{:<18}", snippet.name)),
+                    Span::styled(
+                        snippet.description.clone(),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ]))
+            })
+            .collect();
+        if items.is_empty() {
+            items.push(
+                ListItem::new("No matching snippets").style(Style::default().fg(Color::Gray)),
+            );
+        }
+        let height = (items.len() as u16 + 2).min(area.height);
+        let [_, popup_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(height)]).areas(area);
+        let [popup_area, _] =
+            Layout::horizontal([Constraint::Length(72), Constraint::Min(0)]).areas(popup_area);
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Blue))
+                    .title(format!(" Snippet: {}_ ", self.query)),
+            )
+            .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black));
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(list, popup_area, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::Content;
+
+    #[test]
+    fn test_snippet_is_inserted_with_tab_stops() {
+        let snippet = Snippet {
+            name: "option".to_string(),
+            description: String::new(),
+            sections: Vec::new(),
+            body: "| `${1:--flag}` | ${2:none} |\n$0".to_string(),
+        };
+        let (lines, stops) = snippet.expand();
+        assert_eq!(lines, vec!["| `--flag` | none |", ""]);
+
+        let mut content = Content::new();
+        content.content_input = vec!["Options: ".to_string()];
+        content.cursor_index_x = 9;
+        content.insert_snippet(&lines, &stops);
+        assert_eq!(content.cursor_index_x, 12);
+        for c in "--out".chars() {
+            content.insert_char(c);
+        }
+        content.next_tab_stop();
+        assert_eq!(content.cursor_index_x, 21);
+        content.insert_char('-');
+        assert_eq!(content.content_input, vec!["Options: | `--out` | - |", ""]);
+        content.next_tab_stop();
+        assert_eq!((content.cursor_index_y, content.cursor_index_x), (1, 0));
+        assert!(!content.has_tab_stops());
+    }
+
+    #[test]
+    fn test_library_is_filtered_by_section_and_query() {
+        let config = LoadedConfig {
+            path: ".txtui.toml".into(),
+            file: toml::from_str(
+                "[snippets.admonition]\nsections = [\"reference\"]\nbody = \"> **Warning**: $0\"\n\n[snippets.endpoint]\nsections = [\"reference\"]\ndescription = \"HTTP endpoint\"\nbody = \"### ${1:GET} ${2:/path}\"\n",
+            )
+            .unwrap(),
+        };
+        let library = SnippetLibrary::from_configs(&[config]).unwrap();
+        let names = |snippets: Vec<Snippet>| -> Vec<String> {
+            snippets.into_iter().map(|snippet| snippet.name).collect()
+        };
+        assert_eq!(
+            names(library.for_section(&ContentListItem::Tutorials)),
+            vec!["prerequisites", "step"]
+        );
+
+        let mut picker = SnippetPicker::new();
+        picker.open(library.for_section(&ContentListItem::Reference));
+        for c in "table".chars() {
+            picker.insert_char(c);
+        }
+        assert_eq!(
+            picker.selected().map(|s| s.name.as_str()),
+            Some("option-table")
+        );
+        for _ in 0..5 {
+            picker.delete_char();
+        }
+        for c in "http".chars() {
+            picker.insert_char(c);
+        }
+        assert_eq!(picker.selected().map(|s| s.name.as_str()), Some("endpoint"));
+
+        let invalid = LoadedConfig {
+            path: ".txtui.toml".into(),
+            file: toml::from_str("[snippets.note]\nsections = [\"intro\"]\nbody = \"\"\n").unwrap(),
+        };
+        assert!(SnippetLibrary::from_configs(&[invalid]).is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use crate::config::LoadedConfig;
+use crate::content::{Content, ContentListItem};
+use crate::front_matter::PageMetadata;
+use crate::sections::{section_for_name, section_key};
+
+pub const DEFAULT_SOURCE_DIR: &str = ".txtui/doc";
+pub const MANIFEST_FILE: &str = "manifest.toml";
+const MANIFEST_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Page {
+    section: String,
+    file: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SessionEntry {
+    section: Option<String>,
+    #[serde(default)]
+    line: usize,
+    #[serde(default)]
+    column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    version: u32,
+    #[serde(default)]
+    pages: Vec<Page>,
+    #[serde(default)]
+    session: SessionEntry,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    front_matter: BTreeMap<String, PageMetadata>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub section: ContentListItem,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SavedDocument {
+    pub sections: HashMap<ContentListItem, Vec<String>>,
+    pub front_matter: HashMap<ContentListItem, PageMetadata>,
+    pub cursor: Option<Cursor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentSource {
+    pub dir: PathBuf,
+}
+
+impl Default for DocumentSource {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from(DEFAULT_SOURCE_DIR),
+        }
+    }
+}
+
+fn section_for_manifest_key(path: &Path, key: &str) -> Result<ContentListItem> {
+    section_for_name(key)
+        .cloned()
+        .ok_or_else(|| eyre!("{}: unknown section '{}'", path.display(), key))
+}
+
+fn page_path(dir: &Path, manifest_path: &Path, file: &str) -> Result<PathBuf> {
+    let inside = Path::new(file)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if !inside || file.is_empty() {
+        return Err(eyre!(
+            "{}: page file '{}' must be a path inside {}",
+            manifest_path.display(),
+            file,
+            dir.display()
+        ));
+    }
+    Ok(dir.join(file))
+}
+
+pub fn write_atomic(path: &Path, data: &str) -> io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(data.as_bytes())?;
+    file.persist(path).map(|_| ()).map_err(|error| error.error)
+}
+
+fn read_lines(path: &Path) -> Result<Vec<String>> {
+    let data =
+        fs::read_to_string(path).wrap_err_with(|| format!("Unable to read {}", path.display()))?;
+    Ok(data.lines().map(|line| line.to_string()).collect())
+}
+
+impl DocumentSource {
+    pub fn from_configs(configs: &[LoadedConfig]) -> Result<Self> {
+        let mut source = DocumentSource::default();
+        for config in configs {
+            if let Some(dir) = &config.file.source.dir {
+                source.dir = config.resolve_path(dir);
+            }
+        }
+        Ok(source)
+    }
+
+    pub fn manifest_path(&self) -> PathBuf {
+        self.dir.join(MANIFEST_FILE)
+    }
+
+    pub fn exists(&self) -> bool {
+        self.manifest_path().is_file()
+    }
+
+    pub fn load(&self) -> Result<Option<SavedDocument>> {
+        let manifest_path = self.manifest_path();
+        if !manifest_path.is_file() {
+            return Ok(None);
+        }
+        let data = fs::read_to_string(&manifest_path)
+            .wrap_err_with(|| format!("Unable to read {}", manifest_path.display()))?;
+        let manifest: Manifest = toml::from_str(&data)
+            .wrap_err_with(|| format!("Invalid document manifest {}", manifest_path.display()))?;
+        if manifest.version > MANIFEST_VERSION {
+            return Err(eyre!(
+                "{} was written by a newer txtui (version {}), update txtui to open it",
+                manifest_path.display(),
+                manifest.version
+            ));
+        }
+        let mut document = SavedDocument::default();
+        for page in &manifest.pages {
+            let section = section_for_manifest_key(&manifest_path, &page.section)?;
+            let path = page_path(&self.dir, &manifest_path, &page.file)?;
+            document.sections.insert(section, read_lines(&path)?);
+        }
+        for (key, metadata) in manifest.front_matter {
+            document
+                .front_matter
+                .insert(section_for_manifest_key(&manifest_path, &key)?, metadata);
+        }
+        if let Some(key) = &manifest.session.section {
+            document.cursor = Some(Cursor {
+                section: section_for_manifest_key(&manifest_path, key)?,
+                line: manifest.session.line,
+                column: manifest.session.column,
+            });
+        }
+        Ok(Some(document))
+    }
+
+    pub fn save(
+        &self,
+        sections: &HashMap<ContentListItem, Vec<String>>,
+        front_matter: &HashMap<ContentListItem, PageMetadata>,
+        cursor: Option<&Cursor>,
+    ) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let mut pages = Vec::new();
+        for section in Content::sections() {
+            let Some(lines) = sections.get(&section) else {
+                continue;
+            };
+            let file = format!("{}.md", section_key(&section));
+            let mut data = lines.join("\n");
+            data.push('\n');
+            write_atomic(&self.dir.join(&file), &data)?;
+            pages.push(Page {
+                section: section_key(&section).to_string(),
+                file,
+            });
+        }
+        let manifest = Manifest {
+            version: MANIFEST_VERSION,
+            pages,
+            session: cursor
+                .map(|cursor| SessionEntry {
+                    section: Some(section_key(&cursor.section).to_string()),
+                    line: cursor.line,
+                    column: cursor.column,
+                })
+                .unwrap_or_default(),
+            front_matter: front_matter
+                .iter()
+                .filter(|(_, metadata)| !metadata.is_empty())
+                .map(|(section, metadata)| (section_key(section).to_string(), metadata.clone()))
+                .collect(),
+        };
+        let data = toml::to_string(&manifest).map_err(io::Error::other)?;
+        write_atomic(&self.manifest_path(), &data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+    use std::{env, process};
+
+    #[test]
+    fn test_saved_document_is_loaded_with_cursor() {
+        let dir = env::temp_dir().join(format!("txtui-source-test-{}", process::id()));
+        let source = DocumentSource { dir: dir.clone() };
+        assert_eq!(source.load().unwrap(), None);
+
+        let sections = HashMap::from([
+            (ContentListItem::ProjectName, lines(&["txtui"])),
+            (ContentListItem::Guides, lines(&["# Build", "", "text"])),
+        ]);
+        let cursor = Cursor {
+            section: ContentListItem::Guides,
+            line: 2,
+            column: 3,
+        };
+        let front_matter = HashMap::from([(
+            ContentListItem::Guides,
+            PageMetadata {
+                title: Some("Guides".to_string()),
+                weight: Some(2),
+                ..PageMetadata::default()
+            },
+        )]);
+        source
+            .save(&sections, &front_matter, Some(&cursor))
+            .unwrap();
+        assert!(dir.join("guides.md").is_file());
+        assert_eq!(
+            source.load().unwrap(),
+            Some(SavedDocument {
+                sections,
+                front_matter,
+                cursor: Some(cursor)
+            })
+        );
+
+        fs::write(
+            source.manifest_path(),
+            "version = 1\n[[pages]]\nsection = \"intro\"\nfile = \"intro.md\"\n",
+        )
+        .unwrap();
+        assert!(source.load().is_err());
+        for file in ["../outside.md", "/etc/hostname", ""] {
+            fs::write(
+                source.manifest_path(),
+                format!(
+                    "version = 2\n[[pages]]\nsection = \"guides\"\nfile = \"{}\"\n",
+                    file
+                ),
+            )
+            .unwrap();
+            assert!(source.load().is_err());
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
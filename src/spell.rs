@@ -0,0 +1,410 @@
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use regex::Regex;
+use spellbook::Dictionary;
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use crate::config::LoadedConfig;
+use crate::content::Highlight;
+
+pub const DEFAULT_LANGUAGE: &str = "en_US";
+pub const DEFAULT_WORD_LIST: &str = ".txtui-words.txt";
+const MAX_SUGGESTIONS: usize = 8;
+
+static WORD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[\p{L}][\p{L}\p{N}'’]*").expect("valid regex"));
+static SKIPPED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"`[^`]*`|\]\([^)]*\)|<[^>]*>|(https?|ftp)://\S+|www\.\S+|\S+@\S+\.\S+")
+        .expect("valid regex")
+});
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Misspelling {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub word: String,
+}
+
+pub struct SpellChecker {
+    pub enabled: bool,
+    pub language: String,
+    pub suggestions: SuggestionPopup,
+    dictionary: Option<Dictionary>,
+    dictionary_dirs: Vec<PathBuf>,
+    word_list: PathBuf,
+}
+
+impl Default for SpellChecker {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            language: DEFAULT_LANGUAGE.to_string(),
+            suggestions: SuggestionPopup::new(),
+            dictionary: None,
+            dictionary_dirs: Vec::new(),
+            word_list: PathBuf::from(DEFAULT_WORD_LIST),
+        }
+    }
+}
+
+fn dictionary_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(data_home) = env::var("XDG_DATA_HOME") {
+        if !data_home.is_empty() {
+            dirs.push(Path::new(&data_home).join("txtui").join("dictionaries"));
+        }
+    }
+    if let Ok(home) = env::var("HOME").or_else(|_| env::var("USERPROFILE")) {
+        let home = Path::new(&home);
+        dirs.push(home.join(".local/share/txtui/dictionaries"));
+        dirs.push(home.join("Library/Spelling"));
+    }
+    for dir in [
+        "/usr/share/hunspell",
+        "/usr/share/myspell",
+        "/usr/share/myspell/dicts",
+        "/usr/local/share/hunspell",
+        "/Library/Spelling",
+    ] {
+        dirs.push(PathBuf::from(dir));
+    }
+    dirs
+}
+
+impl SpellChecker {
+    pub fn from_configs(configs: &[LoadedConfig]) -> Result<Self> {
+        let mut checker = SpellChecker::default();
+        let mut dirs = Vec::new();
+        for config in configs {
+            let spell = &config.file.spell;
+            if let Some(enabled) = spell.enabled {
+                checker.enabled = enabled;
+            }
+            if let Some(language) = &spell.language {
+                checker.language = language.clone();
+            }
+            if let Some(word_list) = &spell.word_list {
+                checker.word_list = config.resolve_path(word_list);
+            }
+            if let Some(dir) = &spell.dictionary_dir {
+                dirs.push(config.resolve_path(dir));
+            }
+        }
+        dirs.extend(dictionary_dirs());
+        checker.dictionary_dirs = dirs;
+        Ok(checker)
+    }
+
+    pub fn load(&mut self) -> Result<()> {
+        if self.dictionary.is_some() {
+            return Ok(());
+        }
+        let dir = self
+            .dictionary_dirs
+            .iter()
+            .find(|dir| {
+                dir.join(format!("{}.aff", self.language)).is_file()
+                    && dir.join(format!("{}.dic", self.language)).is_file()
+            })
+            .cloned()
+            .ok_or_else(|| {
+                eyre!(
+                    "No {} Hunspell dictionary found - install {}.aff/{}.dic or set [spell] dictionary_dir",
+                    self.language,
+                    self.language,
+                    self.language
+                )
+            })?;
+        self.load_dictionary(&dir)
+    }
+
+    fn load_dictionary(&mut self, dir: &Path) -> Result<()> {
+        let read = |extension: &str| {
+            let path = dir.join(format!("{}.{}", self.language, extension));
+            fs::read_to_string(&path)
+                .wrap_err_with(|| format!("Unable to read dictionary {}", path.display()))
+        };
+        let mut dictionary = Dictionary::new(&read("aff")?, &read("dic")?)
+            .map_err(|error| eyre!("Invalid dictionary {}: {}", self.language, error))?;
+        if let Ok(words) = fs::read_to_string(&self.word_list) {
+            for word in words.lines().map(str::trim).filter(|word| !word.is_empty()) {
+                let _ = dictionary.add(word);
+            }
+        }
+        self.dictionary = Some(dictionary);
+        Ok(())
+    }
+
+    pub fn toggle(&mut self) -> Option<String> {
+        if !self.enabled {
+            if let Err(error) = self.load() {
+                return Some(format!("{:#}", error));
+            }
+        }
+        self.enabled = !self.enabled;
+        None
+    }
+
+    fn is_ignored(word: &str) -> bool {
+        let letters: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+        word.chars().any(|c| c.is_numeric())
+            || (letters.len() > 1 && letters.iter().all(|c| c.is_uppercase()))
+            || letters.iter().skip(1).any(|c| c.is_uppercase())
+    }
+
+    pub fn misspellings(&self, lines: &[String]) -> Vec<Misspelling> {
+        let Some(dictionary) = &self.dictionary else {
+            return Vec::new();
+        };
+        let mut misspellings = Vec::new();
+        let mut in_code_block = false;
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+                in_code_block = !in_code_block;
+                continue;
+            }
+            if in_code_block || line.starts_with("    ") || line.starts_with('\t') {
+                continue;
+            }
+            let skipped: Vec<(usize, usize)> = SKIPPED
+                .find_iter(line)
+                .map(|found| (found.start(), found.end()))
+                .collect();
+            for found in WORD.find_iter(line) {
+                if skipped
+                    .iter()
+                    .any(|(start, end)| found.start() >= *start && found.start() < *end)
+                {
+                    continue;
+                }
+                let word = found.as_str().trim_end_matches(['\'', '’']);
+                if SpellChecker::is_ignored(word) || dictionary.check(word) {
+                    continue;
+                }
+                misspellings.push(Misspelling {
+                    line: index,
+                    start: found.start(),
+                    end: found.start() + word.len(),
+                    word: word.to_string(),
+                });
+            }
+        }
+        misspellings
+    }
+
+    pub fn misspelling_at(
+        &self,
+        lines: &[String],
+        line: usize,
+        column: usize,
+    ) -> Option<Misspelling> {
+        self.misspellings(lines)
+            .into_iter()
+            .find(|found| found.line == line && found.start <= column && column <= found.end)
+    }
+
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let mut suggestions = Vec::new();
+        if let Some(dictionary) = &self.dictionary {
+            dictionary.suggest(word, &mut suggestions);
+        }
+        suggestions.truncate(MAX_SUGGESTIONS);
+        suggestions
+    }
+
+    pub fn add_word(&mut self, word: &str) -> Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.word_list)
+            .wrap_err_with(|| format!("Unable to open {}", self.word_list.display()))?;
+        writeln!(file, "{}", word)
+            .wrap_err_with(|| format!("Unable to write {}", self.word_list.display()))?;
+        if let Some(dictionary) = &mut self.dictionary {
+            let _ = dictionary.add(word);
+        }
+        Ok(())
+    }
+
+    pub fn highlights(&self, lines: &[String]) -> Vec<Highlight> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        self.misspellings(lines)
+            .into_iter()
+            .map(|found| Highlight {
+                line: found.line,
+                start: found.start,
+                end: found.end,
+                style: Style::default()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::UNDERLINED),
+            })
+            .collect()
+    }
+}
+
+pub enum SuggestionChoice {
+    Replace(String),
+    AddToWordList,
+}
+
+#[derive(Default)]
+pub struct SuggestionPopup {
+    pub active: bool,
+    pub misspelling: Option<Misspelling>,
+    suggestions: Vec<String>,
+    state: ListState,
+}
+
+impl SuggestionPopup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, misspelling: Misspelling, suggestions: Vec<String>) {
+        self.active = true;
+        self.misspelling = Some(misspelling);
+        self.suggestions = suggestions;
+        self.state.select(Some(0));
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.misspelling = None;
+        self.suggestions.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.suggestions.len() + 1
+    }
+
+    pub fn next(&mut self) {
+        let selected = self.state.selected().unwrap_or(0);
+        self.state.select(Some((selected + 1) % self.len()));
+    }
+
+    pub fn previous(&mut self) {
+        let selected = self.state.selected().unwrap_or(0);
+        self.state
+            .select(Some((selected + self.len() - 1) % self.len()));
+    }
+
+    pub fn choice(&self) -> SuggestionChoice {
+        match self.suggestions.get(self.state.selected().unwrap_or(0)) {
+            Some(suggestion) => SuggestionChoice::Replace(suggestion.clone()),
+            None => SuggestionChoice::AddToWordList,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let Some(misspelling) = &self.misspelling else {
+            return;
+        };
+        let mut items: Vec<ListItem> = self
+            .suggestions
+            .iter()
+            .map(|suggestion| ListItem::new(suggestion.as_str()))
+            .collect();
+        items.push(ListItem::new("+ Add to word list").style(Style::default().fg(Color::Gray)));
+        let height = (items.len() as u16 + 2).min(area.height);
+        let [_, popup_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(height)]).areas(area);
+        let [popup_area, _] =
+            Layout::horizontal([Constraint::Length(36), Constraint::Min(0)]).areas(popup_area);
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Blue))
+                    .title(format!(" {} ", misspelling.word)),
+            )
+            .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black));
+        frame.render_widget(Clear, popup_area);
+        frame.render_stateful_widget(list, popup_area, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+
+    fn checker() -> SpellChecker {
+        let dictionary = Dictionary::new(
+            "SET UTF-8\nTRY esiarntolcdugmphbyfvkwz\n",
+            "5\nrun\nthe\ntests\nwith\ncargo\n",
+        )
+        .unwrap();
+        SpellChecker {
+            enabled: true,
+            dictionary: Some(dictionary),
+            word_list: env::temp_dir().join(format!("txtui-{}-words.txt", std::process::id())),
+            ..SpellChecker::default()
+        }
+    }
+
+    #[test]
+    fn test_misspellings_skip_code_and_urls() {
+        let checker = checker();
+        let found = checker.misspellings(&lines(&[
+            "Run teh tests with `crago`",
+            "```",
+            "crago tset",
+            "```",
+            "https://exmaple.com [the](docs/tset.md) API txtui2",
+        ]));
+        let words: Vec<(usize, &str)> = found
+            .iter()
+            .map(|found| (found.line, found.word.as_str()))
+            .collect();
+        assert_eq!(words, vec![(0, "teh")]);
+        assert_eq!((found[0].start, found[0].end), (4, 7));
+    }
+
+    #[test]
+    fn test_suggestions_and_word_list() {
+        let mut checker = checker();
+        assert!(checker.suggest("teh").contains(&"the".to_string()));
+
+        let text = lines(&["run txtui"]);
+        assert!(checker.misspelling_at(&text, 0, 5).is_some());
+        checker.add_word("txtui").unwrap();
+        assert!(checker.misspellings(&text).is_empty());
+        let saved = fs::read_to_string(&checker.word_list).unwrap();
+        let _ = fs::remove_file(&checker.word_list);
+        assert_eq!(saved, "txtui\n");
+    }
+
+    #[test]
+    fn test_dictionary_loads_on_first_toggle() {
+        let dir = env::temp_dir().join(format!("txtui-dictionaries-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("en_US.aff"), [0xff, 0xfe]).unwrap();
+        fs::write(dir.join("en_US.dic"), "1\nthe\n").unwrap();
+        let mut checker = SpellChecker {
+            dictionary_dirs: vec![dir.clone()],
+            ..SpellChecker::default()
+        };
+        let message = checker.toggle();
+        assert!(!checker.enabled);
+        assert!(message.is_some_and(|message| message.contains("Unable to read dictionary")));
+
+        fs::write(dir.join("en_US.aff"), "SET UTF-8\n").unwrap();
+        assert_eq!(checker.toggle(), None);
+        let _ = fs::remove_dir_all(&dir);
+        assert!(checker.enabled);
+        assert_eq!(checker.misspellings(&lines(&["the teh"])).len(), 1);
+    }
+}
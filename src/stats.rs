@@ -0,0 +1,354 @@
+use color_eyre::eyre::{eyre, Result};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Row, Table},
+    Frame,
+};
+use regex::Regex;
+use std::{collections::HashMap, sync::LazyLock};
+
+use crate::config::LoadedConfig;
+use crate::content::ContentListItem;
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::lint::prose_lines;
+use crate::markdown;
+use crate::sections::{section_for_key, SectionDefinitions};
+
+pub const DEFAULT_WORDS_PER_MINUTE: usize = 200;
+
+static WORD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[\p{L}\p{N}][\p{L}\p{N}'’-]*").expect("valid regex"));
+static SENTENCE_END: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[.!?]+(\s|$)").expect("valid regex"));
+static LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(!?)\[([^\]]*)\]\([^)]*\)").expect("valid regex"));
+static CODE_SPAN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`[^`]*`").expect("valid regex"));
+static BARE_URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<?[A-Za-z][A-Za-z0-9+.-]*://\S+").expect("valid regex"));
+static VOWELS: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[aeiouy]+").expect("valid regex"));
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub words: usize,
+    pub sentences: usize,
+    pub syllables: usize,
+    pub code_blocks: usize,
+    pub links: usize,
+    pub images: usize,
+}
+
+fn syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    let mut count = VOWELS.find_iter(&word).count();
+    if count > 1 && word.ends_with('e') && !word.ends_with("le") {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+impl Stats {
+    pub fn of(lines: &[String]) -> Self {
+        let mut stats = Stats {
+            code_blocks: markdown::code_blocks(lines),
+            ..Stats::default()
+        };
+        for (_, line) in prose_lines(lines) {
+            let line = CODE_SPAN.replace_all(line, "code");
+            for captures in LINK.captures_iter(&line) {
+                if captures[1].is_empty() {
+                    stats.links += 1;
+                } else {
+                    stats.images += 1;
+                }
+            }
+            let text = LINK.replace_all(&line, |captures: &regex::Captures| {
+                if captures[1].is_empty() {
+                    captures[2].to_string()
+                } else {
+                    String::new()
+                }
+            });
+            let text = BARE_URL.replace_all(&text, "link");
+            for sentence in SENTENCE_END.split(&text) {
+                let words: Vec<&str> = WORD.find_iter(sentence).map(|word| word.as_str()).collect();
+                if words.is_empty() {
+                    continue;
+                }
+                stats.sentences += 1;
+                stats.words += words.len();
+                stats.syllables += words.iter().map(|word| syllables(word)).sum::<usize>();
+            }
+        }
+        stats
+    }
+
+    pub fn add(&mut self, other: &Stats) {
+        self.words += other.words;
+        self.sentences += other.sentences;
+        self.syllables += other.syllables;
+        self.code_blocks += other.code_blocks;
+        self.links += other.links;
+        self.images += other.images;
+    }
+
+    pub fn reading_minutes(&self, words_per_minute: usize) -> usize {
+        self.words.div_ceil(words_per_minute.max(1))
+    }
+
+    pub fn average_sentence_length(&self) -> Option<f64> {
+        (self.sentences > 0).then(|| self.words as f64 / self.sentences as f64)
+    }
+
+    pub fn flesch_reading_ease(&self) -> Option<f64> {
+        let sentence_length = self.average_sentence_length()?;
+        Some(206.835 - 1.015 * sentence_length - 84.6 * self.syllables as f64 / self.words as f64)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WordTarget {
+    pub min_words: Option<usize>,
+    pub max_words: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsTargets {
+    pub words_per_minute: usize,
+    targets: HashMap<ContentListItem, WordTarget>,
+}
+
+impl Default for StatsTargets {
+    fn default() -> Self {
+        Self {
+            words_per_minute: DEFAULT_WORDS_PER_MINUTE,
+            targets: HashMap::from([
+                (
+                    ContentListItem::Tutorials,
+                    WordTarget {
+                        min_words: Some(1),
+                        max_words: None,
+                    },
+                ),
+                (
+                    ContentListItem::Reference,
+                    WordTarget {
+                        min_words: Some(100),
+                        max_words: None,
+                    },
+                ),
+            ]),
+        }
+    }
+}
+
+impl StatsTargets {
+    pub fn from_configs(configs: &[LoadedConfig]) -> Result<Self> {
+        let mut targets = StatsTargets::default();
+        for config in configs {
+            let stats = &config.file.stats;
+            if let Some(words_per_minute) = stats.words_per_minute {
+                if words_per_minute == 0 {
+                    return Err(eyre!(
+                        "{}: words_per_minute must be greater than 0",
+                        config.path.display()
+                    ));
+                }
+                targets.words_per_minute = words_per_minute;
+            }
+            for (name, target) in &stats.targets {
+                let section = section_for_key(config, "stats.targets", name)?;
+                targets.targets.insert(
+                    section.clone(),
+                    WordTarget {
+                        min_words: target.min_words,
+                        max_words: target.max_words,
+                    },
+                );
+            }
+        }
+        Ok(targets)
+    }
+
+    pub fn check(&self, section: &ContentListItem, stats: &Stats) -> Option<String> {
+        let target = self.targets.get(section)?;
+        match (target.min_words, target.max_words) {
+            (Some(1), _) if stats.words == 0 => Some("Section is empty".to_string()),
+            (Some(min), _) if stats.words < min => Some(format!(
+                "{} words, the target is at least {}",
+                stats.words, min
+            )),
+            (_, Some(max)) if stats.words > max => Some(format!(
+                "{} words, the target is at most {}",
+                stats.words, max
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn check_document(&self, sections: &[(ContentListItem, Vec<String>)]) -> Vec<Diagnostic> {
+        sections
+            .iter()
+            .filter_map(|(section, lines)| {
+                Some(Diagnostic {
+                    section: section.clone(),
+                    line: None,
+                    severity: Severity::Warning,
+                    rule: "word-count",
+                    message: self.check(section, &Stats::of(lines))?,
+                })
+            })
+            .collect()
+    }
+}
+
+fn format_score(score: Option<f64>) -> String {
+    score.map_or("-".to_string(), |score| format!("{:.1}", score))
+}
+
+pub fn render_panel(
+    frame: &mut Frame,
+    area: Rect,
+    sections: &[(ContentListItem, Stats)],
+    targets: &StatsTargets,
+    definitions: &SectionDefinitions,
+) {
+    let mut total = Stats::default();
+    let mut rows: Vec<Row> = Vec::new();
+    let mut push_row = |name: String, stats: &Stats, target: Cell<'static>, style: Style| {
+        rows.push(
+            Row::new(vec![
+                Cell::from(name),
+                Cell::from(stats.words.to_string()),
+                Cell::from(format!(
+                    "{} min",
+                    stats.reading_minutes(targets.words_per_minute)
+                )),
+                Cell::from(format_score(stats.average_sentence_length())),
+                Cell::from(format_score(stats.flesch_reading_ease())),
+                Cell::from(stats.code_blocks.to_string()),
+                Cell::from(stats.links.to_string()),
+                Cell::from(stats.images.to_string()),
+                target,
+            ])
+            .style(style),
+        )
+    };
+    for (section, stats) in sections {
+        total.add(stats);
+        let name = definitions.label(section).to_string();
+        let target = match targets.check(section, stats) {
+            Some(message) => Cell::from(message).style(Style::default().fg(Color::Yellow)),
+            None => Cell::from("ok").style(Style::default().fg(Color::Green)),
+        };
+        push_row(name, stats, target, Style::default());
+    }
+    push_row(
+        "Document".to_string(),
+        &total,
+        Cell::from(""),
+        Style::default().add_modifier(Modifier::BOLD),
+    );
+    let header = Row::new(vec![
+        "Section", "Words", "Reading", "Sentence", "Flesch", "Code", "Links", "Images", "Target",
+    ])
+    .style(Style::default().fg(Color::Gray));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(14),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(9),
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Length(6),
+            Constraint::Length(7),
+            Constraint::Min(10),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue))
+            .title(" Statistics "),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(table, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigFile;
+    use crate::test_support::lines;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_stats_count_prose_and_markup() {
+        let stats = Stats::of(&lines(&[
+            "Run the tests. See [the guide](guide.md) for `cargo` details!",
+            "![diagram](diagram.png)",
+            "```sh",
+            "cargo test --workspace",
+            "```",
+        ]));
+        assert_eq!(stats.words, 9);
+        assert_eq!(stats.sentences, 2);
+        assert_eq!((stats.code_blocks, stats.links, stats.images), (1, 1, 1));
+        assert_eq!(stats.average_sentence_length(), Some(4.5));
+        assert!(stats.flesch_reading_ease().unwrap() > 80.0);
+        assert_eq!(Stats::default().flesch_reading_ease(), None);
+
+        let stats = Stats::of(&lines(&[
+            "````md",
+            "```sh",
+            "echo nested words here",
+            "```",
+            "````",
+            "~~~",
+            "unclosed",
+        ]));
+        assert_eq!((stats.code_blocks, stats.words), (2, 0));
+    }
+
+    #[test]
+    fn test_targets_are_configurable() {
+        let targets = StatsTargets::default();
+        let empty = Stats::default();
+        assert_eq!(
+            targets.check(&ContentListItem::Tutorials, &empty),
+            Some("Section is empty".to_string())
+        );
+        assert!(targets
+            .check(&ContentListItem::Reference, &Stats::of(&lines(&["Short."])))
+            .is_some());
+        assert_eq!(targets.check(&ContentListItem::Guides, &empty), None);
+
+        let file: ConfigFile = toml::from_str(
+            "[stats]\nwords_per_minute = 100\n[stats.targets.guides]\nmax_words = 1\n[stats.targets.reference]",
+        )
+        .unwrap();
+        let config = LoadedConfig {
+            path: PathBuf::from(".txtui.toml"),
+            file,
+        };
+        let targets = StatsTargets::from_configs(&[config]).unwrap();
+        assert_eq!(targets.words_per_minute, 100);
+        assert_eq!(targets.check(&ContentListItem::Reference, &empty), None);
+        assert!(targets
+            .check(
+                &ContentListItem::Guides,
+                &Stats::of(&lines(&["Two words."]))
+            )
+            .is_some());
+
+        let file: ConfigFile = toml::from_str("[stats.targets.intro]\nmin_words = 1").unwrap();
+        let config = LoadedConfig {
+            path: PathBuf::from(".txtui.toml"),
+            file,
+        };
+        assert!(StatsTargets::from_configs(&[config]).is_err());
+    }
+}
@@ -0,0 +1,889 @@
+use color_eyre::eyre::{Result, WrapErr};
+use regex::Regex;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    sync::LazyLock,
+};
+
+use crate::config::Source;
+use crate::content::{Content, ContentListItem};
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::document::{self, RenderOptions};
+use crate::markdown;
+use crate::metadata::{Item, LISTS};
+use crate::sections::{section_key, SectionDefinitions};
+
+pub const FRONT_MATTER_DELIMITER: &str = "---";
+const MAX_STEPS: usize = 10_000;
+
+static VARIABLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{([^{}]*)\}\}").expect("valid regex"));
+static CONDITION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^(!)?\s*([A-Za-z0-9_.-]+)\s*(?:(==|!=)\s*"([^"]*)")?$"#).expect("valid regex")
+});
+static IDENTIFIER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z0-9_.-]+$").expect("valid regex"));
+static FRONT_MATTER_ENTRY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-Za-z0-9_-]+):\s*(.*)$").expect("valid regex"));
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Template {
+    pub front_matter: BTreeMap<String, String>,
+    pub body: Vec<String>,
+    pub body_line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateError {
+    pub line: usize,
+    pub rule: &'static str,
+    pub message: String,
+}
+
+pub type TemplateRead = Result<(Vec<String>, Vec<TemplateError>), String>;
+
+fn front_matter_error(line: usize, message: String) -> TemplateError {
+    TemplateError {
+        line,
+        rule: "front-matter",
+        message,
+    }
+}
+
+pub fn parse(lines: &[String]) -> (Template, Vec<TemplateError>) {
+    let mut template = Template::default();
+    let mut errors = Vec::new();
+    if lines.first().map(|line| line.trim()) != Some(FRONT_MATTER_DELIMITER) {
+        template.body = lines.to_vec();
+        return (template, errors);
+    }
+    let Some(end) = lines
+        .iter()
+        .skip(1)
+        .position(|line| line.trim() == FRONT_MATTER_DELIMITER)
+        .map(|index| index + 1)
+    else {
+        errors.push(front_matter_error(
+            0,
+            "Front matter is not closed with ---".to_string(),
+        ));
+        template.body = lines.to_vec();
+        return (template, errors);
+    };
+    for (index, line) in lines.iter().enumerate().take(end).skip(1) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let Some(captures) = FRONT_MATTER_ENTRY.captures(trimmed) else {
+            errors.push(front_matter_error(
+                index,
+                format!("Expected `key: value` in front matter, found `{}`", trimmed),
+            ));
+            continue;
+        };
+        let value = captures[2].trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(value);
+        if template
+            .front_matter
+            .insert(captures[1].to_string(), value.to_string())
+            .is_some()
+        {
+            errors.push(front_matter_error(
+                index,
+                format!("Duplicate front matter key '{}'", &captures[1]),
+            ));
+        }
+    }
+    template.body = lines[end + 1..].to_vec();
+    template.body_line = end + 1;
+    (template, errors)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Context {
+    pub variables: BTreeMap<String, String>,
+    pub lists: BTreeMap<String, Vec<Item>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Condition {
+    negated: bool,
+    name: String,
+    comparison: Option<(bool, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Block {
+    If(Condition),
+    Each(String),
+    Repeat(String),
+}
+
+impl Block {
+    fn name(&self) -> &'static str {
+        match self {
+            Block::If(_) => "if",
+            Block::Each(_) => "each",
+            Block::Repeat(_) => "repeat",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Tag {
+    Open(Block),
+    Else,
+    Close(String),
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Line(usize, String),
+    If {
+        line: usize,
+        condition: Condition,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        line: usize,
+        list: String,
+        body: Vec<Node>,
+    },
+    Repeat {
+        line: usize,
+        count: String,
+        body: Vec<Node>,
+    },
+}
+
+struct Frame {
+    line: usize,
+    block: Block,
+    nodes: Vec<Node>,
+    otherwise: Option<Vec<Node>>,
+}
+
+impl Node {
+    fn line(&self) -> usize {
+        match self {
+            Node::Line(line, _)
+            | Node::If { line, .. }
+            | Node::Each { line, .. }
+            | Node::Repeat { line, .. } => *line,
+        }
+    }
+}
+
+impl Frame {
+    fn into_node(self) -> Node {
+        match self.block {
+            Block::If(condition) => Node::If {
+                line: self.line,
+                condition,
+                then: self.nodes,
+                otherwise: self.otherwise.unwrap_or_default(),
+            },
+            Block::Each(list) => Node::Each {
+                line: self.line,
+                list,
+                body: self.nodes,
+            },
+            Block::Repeat(count) => Node::Repeat {
+                line: self.line,
+                count,
+                body: self.nodes,
+            },
+        }
+    }
+}
+
+fn syntax_error(line: usize, message: String) -> TemplateError {
+    TemplateError {
+        line,
+        rule: "template-syntax",
+        message,
+    }
+}
+
+fn parse_tag(line: &str) -> Option<Tag> {
+    let inner = line.trim().strip_prefix("{{")?.strip_suffix("}}")?.trim();
+    if inner.contains("{{") || inner.contains("}}") {
+        return None;
+    }
+    if inner == "else" {
+        return Some(Tag::Else);
+    }
+    if let Some(name) = inner.strip_prefix('/') {
+        let name = name.trim();
+        return Some(match name {
+            "if" | "each" | "repeat" => Tag::Close(name.to_string()),
+            _ => Tag::Invalid(format!("Unknown block '{{{{/{}}}}}'", name)),
+        });
+    }
+    let inner = inner.strip_prefix('#')?;
+    let (name, argument) = inner
+        .split_once(char::is_whitespace)
+        .map_or((inner, ""), |(name, argument)| (name, argument.trim()));
+    Some(match name {
+        "if" => match CONDITION.captures(argument) {
+            Some(captures) => Tag::Open(Block::If(Condition {
+                negated: captures.get(1).is_some(),
+                name: captures[2].to_string(),
+                comparison: captures
+                    .get(3)
+                    .map(|operator| (operator.as_str() == "==", captures[4].to_string())),
+            })),
+            None => Tag::Invalid(format!(
+                "Invalid condition '{}' - use `name`, `!name`, `name == \"value\"` or `name != \"value\"`",
+                argument
+            )),
+        },
+        "each" if IDENTIFIER.is_match(argument) => Tag::Open(Block::Each(argument.to_string())),
+        "each" => Tag::Invalid("{{#each}} needs the name of a list".to_string()),
+        "repeat" if IDENTIFIER.is_match(argument) => {
+            Tag::Open(Block::Repeat(argument.to_string()))
+        }
+        "repeat" => Tag::Invalid("{{#repeat}} needs a number or a variable name".to_string()),
+        _ => Tag::Invalid(format!("Unknown block '{{{{#{}}}}}'", name)),
+    })
+}
+
+fn is_block_tag(inner: &str) -> bool {
+    let inner = inner.trim();
+    inner.starts_with('#') || inner.starts_with('/') || inner == "else"
+}
+
+fn current<'a>(stack: &'a mut [Frame], root: &'a mut Vec<Node>) -> &'a mut Vec<Node> {
+    match stack.last_mut() {
+        Some(Frame {
+            otherwise: Some(otherwise),
+            ..
+        }) => otherwise,
+        Some(frame) => &mut frame.nodes,
+        None => root,
+    }
+}
+
+fn parse_blocks(template: &Template) -> (Vec<Node>, Vec<TemplateError>) {
+    let mut root = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut errors = Vec::new();
+    let fenced = markdown::fenced_lines(&template.body);
+    for (index, text) in template.body.iter().enumerate() {
+        let line = template.body_line + index;
+        if fenced[index] {
+            current(&mut stack, &mut root).push(Node::Line(line, text.clone()));
+            continue;
+        }
+        match parse_tag(text) {
+            None => {
+                if VARIABLE
+                    .captures_iter(text)
+                    .any(|captures| is_block_tag(&captures[1]))
+                {
+                    errors.push(syntax_error(
+                        line,
+                        "Block tags must be on a line of their own".to_string(),
+                    ));
+                }
+                current(&mut stack, &mut root).push(Node::Line(line, text.clone()));
+            }
+            Some(Tag::Open(block)) => stack.push(Frame {
+                line,
+                block,
+                nodes: Vec::new(),
+                otherwise: None,
+            }),
+            Some(Tag::Else) => match stack.last_mut() {
+                Some(frame) if matches!(frame.block, Block::If(_)) && frame.otherwise.is_none() => {
+                    frame.otherwise = Some(Vec::new())
+                }
+                _ => errors.push(syntax_error(
+                    line,
+                    "{{else}} without a matching {{#if}}".to_string(),
+                )),
+            },
+            Some(Tag::Close(name)) => match stack.pop() {
+                Some(frame) => {
+                    if frame.block.name() != name {
+                        errors.push(syntax_error(
+                            line,
+                            format!(
+                                "{{{{/{}}}}} closes {{{{#{}}}}} opened on line {}",
+                                name,
+                                frame.block.name(),
+                                frame.line + 1
+                            ),
+                        ));
+                    }
+                    let node = frame.into_node();
+                    current(&mut stack, &mut root).push(node);
+                }
+                None => errors.push(syntax_error(
+                    line,
+                    format!("{{{{/{}}}}} without a matching {{{{#{}}}}}", name, name),
+                )),
+            },
+            Some(Tag::Invalid(message)) => errors.push(syntax_error(line, message)),
+        }
+    }
+    while let Some(frame) = stack.pop() {
+        errors.push(syntax_error(
+            frame.line,
+            format!("{{{{#{}}}}} is never closed", frame.block.name()),
+        ));
+        let node = frame.into_node();
+        current(&mut stack, &mut root).push(node);
+    }
+    (root, errors)
+}
+
+fn is_truthy(value: &str) -> bool {
+    !matches!(value.trim(), "" | "false" | "0" | "no")
+}
+
+impl Condition {
+    fn holds(&self, scope: &BTreeMap<String, String>, context: &Context) -> bool {
+        let holds = match (&self.comparison, scope.get(&self.name)) {
+            (Some((equal, expected)), value) => (value == Some(expected)) == *equal,
+            (None, Some(value)) => is_truthy(value),
+            (None, None) => context
+                .lists
+                .get(&self.name)
+                .is_some_and(|items| !items.is_empty()),
+        };
+        holds != self.negated
+    }
+}
+
+fn repeat_count(count: &str, scope: &BTreeMap<String, String>) -> Result<usize, String> {
+    let value = scope.get(count).map_or(count, String::as_str);
+    value
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| format!("{{{{#repeat {}}}}} needs a number, got '{}'", count, value))
+}
+
+fn evaluate(
+    nodes: &[Node],
+    context: &Context,
+    scope: &BTreeMap<String, String>,
+    output: &mut Vec<String>,
+    errors: &mut Vec<TemplateError>,
+    steps: &mut usize,
+) -> bool {
+    for node in nodes {
+        *steps += 1;
+        if *steps > MAX_STEPS {
+            errors.push(syntax_error(
+                node.line(),
+                format!(
+                    "Template expansion stopped after {} steps - check the {{{{#repeat}}}} counts",
+                    MAX_STEPS
+                ),
+            ));
+            return false;
+        }
+        let completed = match node {
+            Node::Line(_, text) => {
+                output.extend(substitute(std::slice::from_ref(text), scope));
+                true
+            }
+            Node::If {
+                condition,
+                then,
+                otherwise,
+                ..
+            } => {
+                let branch = if condition.holds(scope, context) {
+                    then
+                } else {
+                    otherwise
+                };
+                evaluate(branch, context, scope, output, errors, steps)
+            }
+            Node::Each { line, list, body } => {
+                let Some(items) = context.lists.get(list) else {
+                    errors.push(unknown_list(*line, list));
+                    continue;
+                };
+                items.iter().enumerate().all(|(index, item)| {
+                    let mut inner = scope.clone();
+                    inner.extend(item.clone());
+                    inner.insert("index".to_string(), (index + 1).to_string());
+                    evaluate(body, context, &inner, output, errors, steps)
+                })
+            }
+            Node::Repeat { line, count, body } => match repeat_count(count, scope) {
+                Ok(times) => (0..times).all(|index| {
+                    *steps += 1;
+                    let mut inner = scope.clone();
+                    inner.insert("index".to_string(), (index + 1).to_string());
+                    evaluate(body, context, &inner, output, errors, steps)
+                }),
+                Err(message) => {
+                    errors.push(syntax_error(*line, message));
+                    true
+                }
+            },
+        };
+        if !completed {
+            return false;
+        }
+    }
+    true
+}
+
+fn unknown_list(line: usize, list: &str) -> TemplateError {
+    TemplateError {
+        line,
+        rule: "unknown-variable",
+        message: format!(
+            "Unknown list '{}' (available: {})",
+            list,
+            LISTS.map(|(name, _)| name).join(", ")
+        ),
+    }
+}
+
+fn unknown_variable(line: usize, name: &str) -> TemplateError {
+    TemplateError {
+        line,
+        rule: "unknown-variable",
+        message: format!("Unknown variable '{}'", name),
+    }
+}
+
+fn check_nodes(
+    nodes: &[Node],
+    context: &Context,
+    known: &BTreeSet<String>,
+    errors: &mut Vec<TemplateError>,
+) {
+    for node in nodes {
+        match node {
+            Node::Line(line, text) => errors.extend(
+                VARIABLE
+                    .captures_iter(text)
+                    .map(|captures| captures[1].trim().to_string())
+                    .filter(|name| !is_block_tag(name) && !known.contains(name))
+                    .map(|name| unknown_variable(*line, &name)),
+            ),
+            Node::If {
+                line,
+                condition,
+                then,
+                otherwise,
+            } => {
+                if !known.contains(&condition.name) && !context.lists.contains_key(&condition.name)
+                {
+                    errors.push(unknown_variable(*line, &condition.name));
+                }
+                check_nodes(then, context, known, errors);
+                check_nodes(otherwise, context, known, errors);
+            }
+            Node::Each { line, list, body } => {
+                let Some(items) = context.lists.get(list) else {
+                    errors.push(unknown_list(*line, list));
+                    continue;
+                };
+                let mut inner = known.clone();
+                inner.insert("index".to_string());
+                inner.extend(
+                    LISTS
+                        .iter()
+                        .filter(|(name, _)| name == list)
+                        .flat_map(|(_, fields)| fields.iter().map(|field| field.to_string())),
+                );
+                inner.extend(items.iter().flat_map(|item| item.keys().cloned()));
+                check_nodes(body, context, &inner, errors);
+            }
+            Node::Repeat { line, count, body } => {
+                if count.parse::<usize>().is_err() && !known.contains(count) {
+                    errors.push(unknown_variable(*line, count));
+                }
+                let mut inner = known.clone();
+                inner.insert("index".to_string());
+                check_nodes(body, context, &inner, errors);
+            }
+        }
+    }
+}
+
+pub fn context(
+    definitions: &SectionDefinitions,
+    section: &ContentListItem,
+    template: &Template,
+) -> Context {
+    let mut variables = definitions.metadata.variables.clone();
+    variables.insert(
+        "section".to_string(),
+        definitions.heading(section).to_string(),
+    );
+    variables.extend(definitions.variables.clone());
+    variables.extend(template.front_matter.clone());
+    Context {
+        variables,
+        lists: definitions.metadata.lists.clone(),
+    }
+}
+
+fn substitute(lines: &[String], variables: &BTreeMap<String, String>) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            VARIABLE
+                .replace_all(line, |captures: &regex::Captures| {
+                    variables
+                        .get(captures[1].trim())
+                        .cloned()
+                        .unwrap_or_else(|| captures[0].to_string())
+                })
+                .to_string()
+        })
+        .collect()
+}
+
+pub fn expand(template: &Template, context: &Context) -> (Vec<String>, Vec<TemplateError>) {
+    let (nodes, mut errors) = parse_blocks(template);
+    let mut output = Vec::new();
+    evaluate(
+        &nodes,
+        context,
+        &context.variables,
+        &mut output,
+        &mut errors,
+        &mut 0,
+    );
+    (output, errors)
+}
+
+pub fn check(template: &Template, context: &Context) -> Vec<TemplateError> {
+    let (nodes, mut errors) = parse_blocks(template);
+    let known = context.variables.keys().cloned().collect();
+    check_nodes(&nodes, context, &known, &mut errors);
+    if errors.is_empty() {
+        let mut output = Vec::new();
+        evaluate(
+            &nodes,
+            context,
+            &context.variables,
+            &mut output,
+            &mut errors,
+            &mut 0,
+        );
+    }
+    errors.sort_by_key(|error| error.line);
+    errors
+}
+
+pub fn read(
+    definitions: &SectionDefinitions,
+    section: &ContentListItem,
+) -> Result<(Vec<String>, Vec<TemplateError>)> {
+    let path = definitions.template_path(section);
+    let data =
+        fs::read_to_string(&path).wrap_err_with(|| format!("Unable to read {}", path.display()))?;
+    let lines: Vec<String> = data.lines().map(|line| line.to_string()).collect();
+    let (template, mut errors) = parse(&lines);
+    let (expanded, expand_errors) = expand(&template, &context(definitions, section, &template));
+    errors.extend(expand_errors);
+    errors.sort_by_key(|error| error.line);
+    Ok((expanded, errors))
+}
+
+pub fn check_content(content: &Content) -> Vec<Diagnostic> {
+    let definitions = content.definitions();
+    let mut diagnostics = Vec::new();
+    for section in Content::sections() {
+        let path = definitions.template_path(&section);
+        if !path.is_file() {
+            continue;
+        }
+        let problems: Vec<(&'static str, String)> = match content.template_read(&section) {
+            Some(Ok((_, errors))) => errors
+                .iter()
+                .map(|error| {
+                    (
+                        error.rule,
+                        format!("{}:{}: {}", path.display(), error.line + 1, error.message),
+                    )
+                })
+                .collect(),
+            Some(Err(message)) => vec![("template-syntax", message.clone())],
+            None => Vec::new(),
+        };
+        diagnostics.extend(problems.into_iter().map(|(rule, message)| Diagnostic {
+            section: section.clone(),
+            line: None,
+            severity: Severity::Error,
+            rule,
+            message,
+        }));
+    }
+    diagnostics
+}
+
+pub fn list(definitions: &SectionDefinitions, values: &BTreeMap<String, (toml::Value, Source)>) {
+    let source = |key: &str| {
+        values
+            .get(key)
+            .map_or("default".to_string(), |(_, source)| source.to_string())
+    };
+    for section in Content::sections() {
+        let key = section_key(&section);
+        let path = definitions.template_path(&section);
+        let status = if path.is_file() { "" } else { " (missing)" };
+        println!(
+            "{:<12} {}{}  # file name from {}, directory from {}",
+            key,
+            path.display(),
+            status,
+            source(&format!("sections.{}.template", key)),
+            source("templates.dir")
+        );
+    }
+}
+
+pub fn validate(definitions: &SectionDefinitions) -> Result<bool> {
+    let mut problems = 0;
+    for section in Content::sections() {
+        let path = definitions.template_path(&section);
+        if !path.is_file() {
+            println!(
+                "{}: error[missing-template] {:?}: Template file is missing",
+                path.display(),
+                section
+            );
+            problems += 1;
+            continue;
+        }
+        let data = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Unable to read {}", path.display()))?;
+        let lines: Vec<String> = data.lines().map(|line| line.to_string()).collect();
+        let (template, mut errors) = parse(&lines);
+        errors.extend(check(&template, &context(definitions, &section, &template)));
+        for error in &errors {
+            println!(
+                "{}:{}: error[{}] {:?}: {}",
+                path.display(),
+                error.line + 1,
+                error.rule,
+                section,
+                error.message
+            );
+        }
+        problems += errors.len();
+    }
+    println!(
+        "{} problem(s) found in {} template(s)",
+        problems,
+        Content::sections().len()
+    );
+    Ok(problems == 0)
+}
+
+pub fn render(definitions: &SectionDefinitions, render_options: &RenderOptions) -> Vec<String> {
+    let sections: HashMap<ContentListItem, Vec<String>> = Content::sections()
+        .into_iter()
+        .map(|section| {
+            let lines = read(definitions, &section)
+                .map(|(lines, _)| lines)
+                .unwrap_or_default();
+            (section, lines)
+        })
+        .collect();
+    document::render(&sections, render_options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+
+    fn test_context() -> Context {
+        let item = |name: &str| Item::from([("name".to_string(), name.to_string())]);
+        Context {
+            variables: BTreeMap::from([
+                ("cli".to_string(), "true".to_string()),
+                ("kind".to_string(), "service".to_string()),
+                ("steps".to_string(), "2".to_string()),
+            ]),
+            lists: BTreeMap::from([("binaries".to_string(), vec![item("app"), item("appctl")])]),
+        }
+    }
+
+    #[test]
+    fn test_front_matter_and_variables() {
+        let (template, errors) = parse(&lines(&[
+            "---",
+            "team: \"Platform\"",
+            "---",
+            "### {{ section }} for {{team}}",
+            "Ask {{ owner }}",
+        ]));
+        assert!(errors.is_empty());
+        assert_eq!(template.body_line, 3);
+        let mut definitions = SectionDefinitions::default();
+        definitions
+            .variables
+            .insert("channel".to_string(), "#docs".to_string());
+        let context = context(&definitions, &ContentListItem::Guides, &template);
+        assert_eq!(
+            expand(&template, &context).0,
+            lines(&["### Guides for Platform", "Ask {{ owner }}"])
+        );
+        assert_eq!(
+            check(&template, &context),
+            vec![unknown_variable(4, "owner")]
+        );
+
+        let (_, errors) = parse(&lines(&["---", "team Platform", "team: a", "team: b"]));
+        let found: Vec<(usize, &str)> = errors
+            .iter()
+            .map(|error| (error.line, error.message.as_str()))
+            .collect();
+        assert_eq!(found, vec![(0, "Front matter is not closed with ---")]);
+        let (_, errors) = parse(&lines(&[
+            "---",
+            "team Platform",
+            "team: a",
+            "team: b",
+            "---",
+        ]));
+        let found: Vec<usize> = errors.iter().map(|error| error.line).collect();
+        assert_eq!(found, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_conditional_and_repeated_blocks() {
+        let template = Template {
+            body: lines(&[
+                "{{#if cli}}",
+                "### Installation",
+                "{{#each binaries}}",
+                "- {{ index }}. `{{ name }}`",
+                "{{/each}}",
+                "{{/if}}",
+                "{{#if kind == \"library\"}}",
+                "Add the crate",
+                "{{else}}",
+                "Deploy the {{ kind }}",
+                "{{/if}}",
+                "{{#repeat steps}}",
+                "#### Step {{ index }}",
+                "{{/repeat}}",
+            ]),
+            ..Template::default()
+        };
+        let (output, errors) = expand(&template, &test_context());
+        assert!(errors.is_empty());
+        assert_eq!(
+            output,
+            lines(&[
+                "### Installation",
+                "- 1. `app`",
+                "- 2. `appctl`",
+                "Deploy the service",
+                "#### Step 1",
+                "#### Step 2",
+            ])
+        );
+        assert!(check(&template, &test_context()).is_empty());
+    }
+
+    #[test]
+    fn test_fenced_tags_are_text_and_repeats_are_bounded() {
+        let template = Template {
+            body: lines(&[
+                "```handlebars",
+                "{{#if cli}}",
+                "```",
+                "{{#repeat 1000}}",
+                "{{#repeat 1000}}",
+                "{{/repeat}}",
+                "{{/repeat}}",
+            ]),
+            ..Template::default()
+        };
+        let (output, errors) = expand(&template, &test_context());
+        assert_eq!(output, lines(&["```handlebars", "{{#if cli}}", "```"]));
+        let found: Vec<(usize, &str)> = errors
+            .iter()
+            .map(|error| (error.line, error.message.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(
+                4,
+                "Template expansion stopped after 10000 steps - check the {{#repeat}} counts"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_block_errors_point_at_template_lines() {
+        let template = Template {
+            body: lines(&[
+                "{{#if cli}}",
+                "{{#each crates}}",
+                "{{/each}}",
+                "{{#repeat kind}}",
+                "{{/repeat}}",
+                "text {{#if cli}}",
+                "{{/each}}",
+                "{{#unless cli}}",
+                "{{#each binaries}}",
+            ]),
+            body_line: 3,
+            ..Template::default()
+        };
+        let found: Vec<(usize, String)> = check(&template, &test_context())
+            .into_iter()
+            .map(|error| (error.line + 1, error.message))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (
+                    5,
+                    "Unknown list 'crates' (available: binaries, features)".to_string()
+                ),
+                (9, "Block tags must be on a line of their own".to_string()),
+                (10, "{{/each}} closes {{#if}} opened on line 4".to_string()),
+                (11, "Unknown block '{{#unless}}'".to_string()),
+                (12, "{{#each}} is never closed".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_broken_template_is_reported_as_diagnostic() {
+        let dir = std::env::temp_dir().join(format!("txtui-templates-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("guides.md"),
+            "### Guides\n{{#if cli}}\nUse the CLI\n",
+        )
+        .unwrap();
+        let mut definitions = SectionDefinitions::default();
+        definitions.template_dir = dir.clone();
+        let (lines, errors) = read(&definitions, &ContentListItem::Guides).unwrap();
+        let diagnostics = check_content(&Content::with_definitions(definitions));
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(lines[0], "### Guides");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].section, ContentListItem::Guides);
+        assert!(diagnostics[0]
+            .message
+            .ends_with("guides.md:2: {{#if}} is never closed"));
+    }
+}
@@ -0,0 +1,46 @@
+use color_eyre::eyre::{eyre, Result};
+use ratatui::style::Color;
+use std::str::FromStr;
+
+use crate::config::LoadedConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub border: Color,
+    pub highlight: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            border: Color::Red,
+            highlight: Color::Yellow,
+        }
+    }
+}
+
+fn parse_color(config: &LoadedConfig, key: &str, value: &str) -> Result<Color> {
+    Color::from_str(value).map_err(|_| {
+        eyre!(
+            "{}: invalid color '{}' for theme.{} (use a name like \"blue\", an index or \"#rrggbb\")",
+            config.path.display(),
+            value,
+            key
+        )
+    })
+}
+
+impl Theme {
+    pub fn from_configs(configs: &[LoadedConfig]) -> Result<Self> {
+        let mut theme = Theme::default();
+        for config in configs {
+            if let Some(border) = &config.file.theme.border {
+                theme.border = parse_color(config, "border", border)?;
+            }
+            if let Some(highlight) = &config.file.theme.highlight {
+                theme.highlight = parse_color(config, "highlight", highlight)?;
+            }
+        }
+        Ok(theme)
+    }
+}
@@ -0,0 +1,350 @@
+use color_eyre::eyre::{Result, WrapErr};
+use std::{
+    env, fs,
+    io::Read,
+    path::Path,
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::content::ContentListItem;
+use crate::document;
+use crate::sections::SectionDefinitions;
+
+pub const RUNNABLE: &str = "runnable";
+pub const EXPECTED_OUTPUT: &str = "output";
+const SHELLS: [&str; 2] = ["bash", "sh"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub section: ContentListItem,
+    pub line: usize,
+    pub shell: String,
+    pub script: String,
+    pub expected: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed(String),
+    TimedOut,
+    UnexpectedOutput { expected: String, actual: String },
+}
+
+struct Fence {
+    line: usize,
+    info: Vec<String>,
+    body: Vec<String>,
+}
+
+fn fences(lines: &[String]) -> Vec<Fence> {
+    let mut fences = Vec::new();
+    let mut open: Option<Fence> = None;
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        let is_fence = trimmed.starts_with("```") || trimmed.starts_with("~~~");
+        match open.take() {
+            Some(fence) if is_fence => fences.push(fence),
+            Some(mut fence) => {
+                fence.body.push(line.clone());
+                open = Some(fence);
+            }
+            None if is_fence => {
+                open = Some(Fence {
+                    line: index,
+                    info: trimmed
+                        .trim_start_matches(['`', '~'])
+                        .split(|c: char| c.is_whitespace() || c == ',')
+                        .filter(|word| !word.is_empty())
+                        .map(|word| word.trim_matches(['{', '}']).to_lowercase())
+                        .collect(),
+                    body: Vec::new(),
+                })
+            }
+            None => {}
+        }
+    }
+    fences
+}
+
+fn is_blank_between(lines: &[String], from: usize, to: usize) -> bool {
+    lines[from..to].iter().all(|line| line.trim().is_empty())
+}
+
+pub fn steps(section: &ContentListItem, lines: &[String]) -> Vec<Step> {
+    let fences = fences(lines);
+    let mut steps = Vec::new();
+    for (index, fence) in fences.iter().enumerate() {
+        let Some(shell) = fence.info.first() else {
+            continue;
+        };
+        if !SHELLS.contains(&shell.as_str()) || !fence.info.iter().any(|word| word == RUNNABLE) {
+            continue;
+        }
+        let closing_line = fence.line + fence.body.len() + 1;
+        let expected = fences
+            .get(index + 1)
+            .filter(|next| {
+                next.info.first().map(String::as_str) == Some(EXPECTED_OUTPUT)
+                    && is_blank_between(lines, closing_line + 1, next.line)
+            })
+            .map(|next| next.body.join("\n"));
+        steps.push(Step {
+            section: section.clone(),
+            line: fence.line,
+            shell: shell.clone(),
+            script: fence.body.join("\n"),
+            expected,
+        });
+    }
+    steps
+}
+
+fn normalize_output(output: &str) -> String {
+    output
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut output);
+        }
+        output
+    })
+}
+
+#[cfg(unix)]
+fn isolate(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    command.process_group(0);
+}
+
+#[cfg(not(unix))]
+fn isolate(_command: &mut Command) {}
+
+#[cfg(unix)]
+fn kill(child: &mut Child) {
+    // The step runs in its own process group, so this also stops anything it started.
+    let _ = unsafe { libc::killpg(child.id() as libc::pid_t, libc::SIGKILL) };
+}
+
+#[cfg(not(unix))]
+fn kill(child: &mut Child) {
+    let _ = child.kill();
+}
+
+pub fn run_step(step: &Step, work_dir: &Path, project_dir: &Path, timeout: Duration) -> Outcome {
+    let mut command = Command::new(&step.shell);
+    command
+        .arg("-e")
+        .arg("-c")
+        .arg(&step.script)
+        .current_dir(work_dir)
+        .env("TXTUI_PROJECT_DIR", project_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    isolate(&mut command);
+    let child = command.spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(error) => return Outcome::Failed(format!("Unable to start {}: {}", step.shell, error)),
+    };
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                kill(&mut child);
+                let _ = child.wait();
+                return Outcome::TimedOut;
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(20)),
+            Err(error) => return Outcome::Failed(error.to_string()),
+        }
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        let output = normalize_output(&format!("{}{}", stdout, stderr));
+        return Outcome::Failed(format!("{}\n{}", status, output).trim_end().to_string());
+    }
+    match &step.expected {
+        Some(expected) if normalize_output(expected) != normalize_output(&stdout) => {
+            Outcome::UnexpectedOutput {
+                expected: normalize_output(expected),
+                actual: normalize_output(&stdout),
+            }
+        }
+        _ => Outcome::Passed,
+    }
+}
+
+fn indented(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("    {}", line))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn run(path: &Path, definitions: &SectionDefinitions, timeout: Duration) -> Result<bool> {
+    let data =
+        fs::read_to_string(path).wrap_err_with(|| format!("Unable to read {}", path.display()))?;
+    let lines: Vec<String> = data.lines().map(|line| line.to_string()).collect();
+    let parsed = document::parse(&lines, definitions);
+    let steps: Vec<(usize, Step)> = parsed
+        .iter()
+        .filter(|section| {
+            matches!(
+                section.section,
+                ContentListItem::Tutorials | ContentListItem::Guides
+            )
+        })
+        .flat_map(|section| {
+            steps(&section.section, &section.lines)
+                .into_iter()
+                .map(|step| (section.first_line + step.line + 1, step))
+        })
+        .collect();
+
+    let project_dir = env::current_dir().wrap_err("Unable to read the current directory")?;
+    let work_dir = tempfile::Builder::new()
+        .prefix("txtui-verify-")
+        .tempdir()
+        .wrap_err("Unable to create a work directory for the steps")?;
+    let mut failed = 0;
+    for (number, (line_number, step)) in steps.iter().enumerate() {
+        let outcome = run_step(step, work_dir.path(), &project_dir, timeout);
+        let status = match &outcome {
+            Outcome::Passed => "ok".to_string(),
+            Outcome::Failed(_) | Outcome::UnexpectedOutput { .. } => "failed".to_string(),
+            Outcome::TimedOut => format!("timed out after {}s", timeout.as_secs_f32()),
+        };
+        println!(
+            "{}:{}: {:?} step {} ({}): {}",
+            path.display(),
+            line_number,
+            step.section,
+            number + 1,
+            step.shell,
+            status
+        );
+        match outcome {
+            Outcome::Passed => continue,
+            Outcome::Failed(output) => println!("{}", indented(&output)),
+            Outcome::UnexpectedOutput { expected, actual } => {
+                println!("  expected output:\n{}", indented(&expected));
+                println!("  actual output:\n{}", indented(&actual));
+            }
+            Outcome::TimedOut => {}
+        }
+        failed += 1;
+    }
+    println!(
+        "{} of {} step(s) failed in {}",
+        failed,
+        steps.len(),
+        path.display()
+    );
+    Ok(failed == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::lines;
+    use std::process;
+
+    #[test]
+    fn test_runnable_blocks_are_extracted_with_expected_output() {
+        let found = steps(
+            &ContentListItem::Tutorials,
+            &lines(&[
+                "Run `cargo run`",
+                "```bash runnable",
+                "echo hello",
+                "```",
+                "",
+                "```output",
+                "hello",
+                "```",
+                "```sh",
+                "not runnable",
+                "```",
+                "```sh {runnable}",
+                "echo second",
+                "```",
+                "Some text",
+                "```output",
+                "not attached",
+                "```",
+            ]),
+        );
+        let summary: Vec<(usize, &str, Option<&str>)> = found
+            .iter()
+            .map(|step| (step.line, step.script.as_str(), step.expected.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(1, "echo hello", Some("hello")), (11, "echo second", None)]
+        );
+    }
+
+    #[test]
+    fn test_steps_run_in_work_dir_with_timeout() {
+        let work_dir = env::temp_dir().join(format!("txtui-verify-test-{}", process::id()));
+        fs::create_dir_all(&work_dir).unwrap();
+        let step = |script: &str, expected: Option<&str>| Step {
+            section: ContentListItem::Guides,
+            line: 0,
+            shell: "sh".to_string(),
+            script: script.to_string(),
+            expected: expected.map(String::from),
+        };
+        let timeout = Duration::from_secs(5);
+        let run = |step: &Step| run_step(step, &work_dir, Path::new("."), timeout);
+
+        assert_eq!(run(&step("echo kept > file.txt", None)), Outcome::Passed);
+        assert_eq!(run(&step("cat file.txt", Some("kept\n"))), Outcome::Passed);
+        assert_eq!(
+            run(&step("echo other", Some("kept"))),
+            Outcome::UnexpectedOutput {
+                expected: "kept".to_string(),
+                actual: "other".to_string()
+            }
+        );
+        assert!(matches!(run(&step("false", None)), Outcome::Failed(_)));
+        assert_eq!(
+            run_step(
+                &step("sleep 5", None),
+                &work_dir,
+                Path::new("."),
+                Duration::from_millis(100)
+            ),
+            Outcome::TimedOut
+        );
+        assert_eq!(
+            run_step(
+                &step("(sleep 0.3; touch late.txt) & wait", None),
+                &work_dir,
+                Path::new("."),
+                Duration::from_millis(100)
+            ),
+            Outcome::TimedOut
+        );
+        thread::sleep(Duration::from_millis(500));
+        assert!(!work_dir.join("late.txt").exists());
+        let _ = fs::remove_dir_all(&work_dir);
+    }
+}
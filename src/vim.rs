@@ -0,0 +1,979 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::style::{Color, Style};
+
+use crate::content::{Content, Highlight};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimMode {
+    Normal,
+    Insert,
+    Visual,
+    VisualLine,
+    Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimAction {
+    None,
+    Save,
+    Quit,
+    ForceQuit,
+    SaveAndQuit,
+    NextSection,
+    PreviousSection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Delete,
+    Change,
+    Yank,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MotionKind {
+    Exclusive,
+    Inclusive,
+    Linewise,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Motion {
+    target: (usize, usize),
+    kind: MotionKind,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Register {
+    text: Vec<String>,
+    linewise: bool,
+}
+
+pub struct Vim {
+    pub enabled: bool,
+    pub mode: VimMode,
+    pub command_line: String,
+    pub message: Option<String>,
+    count: String,
+    operator: Option<(Operator, usize)>,
+    pending_g: bool,
+    visual_anchor: (usize, usize),
+    register: Register,
+    recording: Vec<KeyEvent>,
+    last_change: Option<Vec<KeyEvent>>,
+    change_made: bool,
+    replaying: bool,
+}
+
+impl Default for Vim {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl Vim {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            mode: VimMode::Normal,
+            command_line: String::new(),
+            message: None,
+            count: String::new(),
+            operator: None,
+            pending_g: false,
+            visual_anchor: (0, 0),
+            register: Register::default(),
+            recording: Vec::new(),
+            last_change: None,
+            change_made: false,
+            replaying: false,
+        }
+    }
+
+    pub fn status(&self) -> String {
+        if let Some(message) = &self.message {
+            return message.clone();
+        }
+        match self.mode {
+            VimMode::Normal => "-- NORMAL --  gt/gT switch section, :w save, :q quit".to_string(),
+            VimMode::Insert => "-- INSERT --".to_string(),
+            VimMode::Visual => "-- VISUAL --".to_string(),
+            VimMode::VisualLine => "-- VISUAL LINE --".to_string(),
+            VimMode::Command => format!(":{}", self.command_line),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.mode = VimMode::Normal;
+        self.clear_pending();
+        self.command_line.clear();
+    }
+
+    fn clear_pending(&mut self) {
+        self.count.clear();
+        self.operator = None;
+        self.pending_g = false;
+    }
+
+    fn is_idle(&self) -> bool {
+        self.mode == VimMode::Normal
+            && self.count.is_empty()
+            && self.operator.is_none()
+            && !self.pending_g
+    }
+
+    fn take_count(&mut self) -> usize {
+        let count = self.count.parse().unwrap_or(1);
+        self.count.clear();
+        count
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, content: &mut Content) -> VimAction {
+        self.message = None;
+        if self.is_idle() && key.code == KeyCode::Char('.') && !self.replaying {
+            if let Some(keys) = self.last_change.clone() {
+                self.replaying = true;
+                for _ in 0..self.take_count() {
+                    for replayed in &keys {
+                        self.dispatch(*replayed, content);
+                    }
+                }
+                self.replaying = false;
+            }
+            return VimAction::None;
+        }
+        if !self.replaying {
+            self.recording.push(key);
+        }
+        let action = self.dispatch(key, content);
+        if !self.replaying && self.is_idle() {
+            if self.change_made {
+                self.last_change = Some(std::mem::take(&mut self.recording));
+            }
+            self.recording.clear();
+            self.change_made = false;
+        }
+        action
+    }
+
+    fn dispatch(&mut self, key: KeyEvent, content: &mut Content) -> VimAction {
+        match self.mode {
+            VimMode::Insert => {
+                self.handle_insert_key(key, content);
+                VimAction::None
+            }
+            VimMode::Command => self.handle_command_key(key, content),
+            VimMode::Normal | VimMode::Visual | VimMode::VisualLine => {
+                self.handle_normal_key(key, content)
+            }
+        }
+    }
+
+    fn handle_insert_key(&mut self, key: KeyEvent, content: &mut Content) {
+        match key.code {
+            KeyCode::Esc => {
+                self.mode = VimMode::Normal;
+                if content.cursor_index_x > 0 {
+                    content.move_cursor_left();
+                }
+            }
+            KeyCode::Char(c) => content.insert_char(c),
+            KeyCode::Enter => content.handle_enter(),
+            KeyCode::Backspace => content.delete_char(),
+            KeyCode::Left => content.move_cursor_left(),
+            KeyCode::Right => content.move_cursor_right(),
+            KeyCode::Up => content.move_cursor_up(),
+            KeyCode::Down => content.move_cursor_down(),
+            _ => {}
+        }
+    }
+
+    fn handle_command_key(&mut self, key: KeyEvent, content: &mut Content) -> VimAction {
+        match key.code {
+            KeyCode::Esc => {
+                self.command_line.clear();
+                self.mode = VimMode::Normal;
+            }
+            KeyCode::Backspace if self.command_line.is_empty() => self.mode = VimMode::Normal,
+            KeyCode::Backspace => {
+                self.command_line.pop();
+            }
+            KeyCode::Char(c) => self.command_line.push(c),
+            KeyCode::Enter => {
+                let command = std::mem::take(&mut self.command_line);
+                self.mode = VimMode::Normal;
+                return self.execute_command(command.trim(), content);
+            }
+            _ => {}
+        }
+        VimAction::None
+    }
+
+    fn execute_command(&mut self, command: &str, content: &mut Content) -> VimAction {
+        match command {
+            "w" => VimAction::Save,
+            "q" => VimAction::Quit,
+            "q!" => VimAction::ForceQuit,
+            "wq" | "x" => VimAction::SaveAndQuit,
+            "" => VimAction::None,
+            _ => {
+                if let Ok(line) = command.parse::<usize>() {
+                    content.cursor_index_y = line
+                        .saturating_sub(1)
+                        .min(content.content_input.len().saturating_sub(1));
+                    content.cursor_index_x = 0;
+                } else {
+                    self.message = Some(format!("Not an editor command: {}", command));
+                }
+                VimAction::None
+            }
+        }
+    }
+
+    fn handle_normal_key(&mut self, key: KeyEvent, content: &mut Content) -> VimAction {
+        let cursor = (content.cursor_index_y, content.cursor_index_x);
+        if let KeyCode::Char(c) = key.code {
+            if c.is_ascii_digit() && (c != '0' || !self.count.is_empty()) {
+                self.count.push(c);
+                return VimAction::None;
+            }
+        }
+        if self.pending_g {
+            self.pending_g = false;
+            match key.code {
+                KeyCode::Char('t') => {
+                    self.clear_pending();
+                    return VimAction::NextSection;
+                }
+                KeyCode::Char('T') => {
+                    self.clear_pending();
+                    return VimAction::PreviousSection;
+                }
+                KeyCode::Char('g') => {
+                    let count = self.count.parse::<usize>().ok();
+                    self.count.clear();
+                    let line = count
+                        .map(|line| line.saturating_sub(1))
+                        .unwrap_or(0)
+                        .min(content.content_input.len().saturating_sub(1));
+                    let text = content
+                        .content_input
+                        .get(line)
+                        .map(String::as_str)
+                        .unwrap_or_default();
+                    let motion = Motion {
+                        target: (line, first_non_blank(text)),
+                        kind: MotionKind::Linewise,
+                    };
+                    self.apply_motion(motion, content);
+                    return VimAction::None;
+                }
+                _ => {
+                    self.clear_pending();
+                    return VimAction::None;
+                }
+            }
+        }
+
+        if self.mode != VimMode::Normal {
+            return self.handle_visual_key(key, content);
+        }
+
+        if let Some((operator, operator_count)) = self.operator {
+            let same_key = matches!(
+                (operator, key.code),
+                (Operator::Delete, KeyCode::Char('d'))
+                    | (Operator::Change, KeyCode::Char('c'))
+                    | (Operator::Yank, KeyCode::Char('y'))
+            );
+            if same_key {
+                let count = operator_count * self.take_count();
+                let last =
+                    (cursor.0 + count - 1).min(content.content_input.len().saturating_sub(1));
+                self.operator = None;
+                self.apply_operator(
+                    operator,
+                    (cursor.0, 0),
+                    Motion {
+                        target: (last, 0),
+                        kind: MotionKind::Linewise,
+                    },
+                    content,
+                );
+                return VimAction::None;
+            }
+        }
+
+        match key.code {
+            KeyCode::Esc => self.clear_pending(),
+            KeyCode::Char(':') => {
+                self.clear_pending();
+                self.mode = VimMode::Command;
+            }
+            KeyCode::Char('g') => self.pending_g = true,
+            KeyCode::Char('d') => self.start_operator(Operator::Delete),
+            KeyCode::Char('c') => self.start_operator(Operator::Change),
+            KeyCode::Char('y') => self.start_operator(Operator::Yank),
+            KeyCode::Char('v') if self.operator.is_none() => {
+                self.clear_pending();
+                self.visual_anchor = cursor;
+                self.mode = VimMode::Visual;
+            }
+            KeyCode::Char('V') if self.operator.is_none() => {
+                self.clear_pending();
+                self.visual_anchor = cursor;
+                self.mode = VimMode::VisualLine;
+            }
+            _ if self.operator.is_some() => {
+                let count = self.take_count();
+                if let Some((operator, operator_count)) = self.operator.take() {
+                    let operator_key = match (operator, key.code) {
+                        (Operator::Change, KeyCode::Char('w')) => KeyCode::Char('e'),
+                        (_, code) => code,
+                    };
+                    if let Some(mut motion) = motion_for_key(
+                        operator_key,
+                        &content.content_input,
+                        cursor,
+                        count * operator_count,
+                    ) {
+                        if key.code == KeyCode::Char('w') && motion.target.0 > cursor.0 {
+                            let line_length = content.content_input[cursor.0].len();
+                            motion.target = (cursor.0, line_length);
+                        }
+                        self.apply_operator(operator, cursor, motion, content);
+                    }
+                }
+            }
+            _ => return self.handle_normal_command(key, content),
+        }
+        VimAction::None
+    }
+
+    fn start_operator(&mut self, operator: Operator) {
+        let count = self.take_count();
+        self.operator = Some((operator, count));
+    }
+
+    fn handle_normal_command(&mut self, key: KeyEvent, content: &mut Content) -> VimAction {
+        let count = self.take_count();
+        let cursor = (content.cursor_index_y, content.cursor_index_x);
+        match key.code {
+            KeyCode::Char('i') => self.enter_insert(content),
+            KeyCode::Char('a') => {
+                self.enter_insert(content);
+                let line_length = current_line(content).len();
+                content.cursor_index_x =
+                    next_char_boundary(current_line(content), cursor.1).min(line_length);
+            }
+            KeyCode::Char('I') => {
+                self.enter_insert(content);
+                content.cursor_index_x = first_non_blank(current_line(content));
+            }
+            KeyCode::Char('A') => {
+                self.enter_insert(content);
+                content.cursor_index_x = current_line(content).len();
+            }
+            KeyCode::Char('o') => {
+                self.enter_insert(content);
+                content.insert_lines(cursor.0 + 1, &[String::new()]);
+            }
+            KeyCode::Char('O') => {
+                self.enter_insert(content);
+                content.insert_lines(cursor.0, &[String::new()]);
+            }
+            KeyCode::Char('x') => {
+                let line = current_line(content);
+                if !line.is_empty() {
+                    let mut end = cursor.1;
+                    for _ in 0..count {
+                        end = next_char_boundary(line, end);
+                    }
+                    content.push_undo();
+                    self.register = Register {
+                        text: content.delete_range(cursor, (cursor.0, end)),
+                        linewise: false,
+                    };
+                    self.change_made = true;
+                    clamp_normal_cursor(content);
+                }
+            }
+            KeyCode::Char('p') | KeyCode::Char('P') => {
+                self.put(key.code == KeyCode::Char('p'), count, content)
+            }
+            KeyCode::Char('u') => {
+                for _ in 0..count {
+                    content.undo();
+                }
+                clamp_normal_cursor(content);
+            }
+            code => {
+                if let Some(motion) = motion_for_key(code, &content.content_input, cursor, count) {
+                    self.apply_motion(motion, content);
+                }
+            }
+        }
+        VimAction::None
+    }
+
+    fn handle_visual_key(&mut self, key: KeyEvent, content: &mut Content) -> VimAction {
+        let cursor = (content.cursor_index_y, content.cursor_index_x);
+        let operator = match key.code {
+            KeyCode::Esc => {
+                self.reset();
+                return VimAction::None;
+            }
+            KeyCode::Char('g') => {
+                self.pending_g = true;
+                return VimAction::None;
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') => {
+                let mode = if key.code == KeyCode::Char('v') {
+                    VimMode::Visual
+                } else {
+                    VimMode::VisualLine
+                };
+                if self.mode == mode {
+                    self.reset();
+                } else {
+                    self.mode = mode;
+                }
+                return VimAction::None;
+            }
+            KeyCode::Char('d') | KeyCode::Char('x') => Operator::Delete,
+            KeyCode::Char('c') => Operator::Change,
+            KeyCode::Char('y') => Operator::Yank,
+            code => {
+                let count = self.take_count();
+                if let Some(motion) = motion_for_key(code, &content.content_input, cursor, count) {
+                    self.apply_motion(motion, content);
+                }
+                return VimAction::None;
+            }
+        };
+        let kind = if self.mode == VimMode::VisualLine {
+            MotionKind::Linewise
+        } else {
+            MotionKind::Inclusive
+        };
+        let anchor = self.visual_anchor;
+        self.reset();
+        self.apply_operator(
+            operator,
+            anchor,
+            Motion {
+                target: cursor,
+                kind,
+            },
+            content,
+        );
+        VimAction::None
+    }
+
+    fn enter_insert(&mut self, content: &mut Content) {
+        content.push_undo();
+        self.change_made = true;
+        self.mode = VimMode::Insert;
+    }
+
+    fn apply_motion(&mut self, motion: Motion, content: &mut Content) {
+        if let Some((operator, _)) = self.operator.take() {
+            let cursor = (content.cursor_index_y, content.cursor_index_x);
+            self.apply_operator(operator, cursor, motion, content);
+            return;
+        }
+        content.cursor_index_y = motion.target.0;
+        content.cursor_index_x = motion.target.1;
+        if self.mode == VimMode::Normal {
+            clamp_normal_cursor(content);
+        }
+    }
+
+    fn apply_operator(
+        &mut self,
+        operator: Operator,
+        origin: (usize, usize),
+        motion: Motion,
+        content: &mut Content,
+    ) {
+        self.clear_pending();
+        let (start, end) = if origin <= motion.target {
+            (origin, motion.target)
+        } else {
+            (motion.target, origin)
+        };
+        if operator != Operator::Yank {
+            content.push_undo();
+            self.change_made = true;
+        }
+        if motion.kind == MotionKind::Linewise {
+            let text = match operator {
+                Operator::Yank => {
+                    let last = end.0.min(content.content_input.len().saturating_sub(1));
+                    content
+                        .content_input
+                        .get(start.0..=last)
+                        .map(<[String]>::to_vec)
+                        .unwrap_or_default()
+                }
+                _ => content.delete_lines(start.0, end.0),
+            };
+            self.register = Register {
+                text,
+                linewise: true,
+            };
+            match operator {
+                Operator::Change => {
+                    let index = start.0.min(content.content_input.len());
+                    if content.content_input.len() == 1 && content.content_input[0].is_empty() {
+                        content.cursor_index_y = 0;
+                    } else {
+                        content.insert_lines(index, &[String::new()]);
+                    }
+                    self.mode = VimMode::Insert;
+                }
+                Operator::Yank => {
+                    content.cursor_index_y = start.0;
+                }
+                Operator::Delete => {
+                    content.cursor_index_x = first_non_blank(current_line(content));
+                }
+            }
+            return;
+        }
+        let end = if motion.kind == MotionKind::Inclusive {
+            let line = content
+                .content_input
+                .get(end.0)
+                .map(String::as_str)
+                .unwrap_or_default();
+            (end.0, next_char_boundary(line, end.1))
+        } else {
+            end
+        };
+        let text = match operator {
+            Operator::Yank => content.text_range(start, end),
+            _ => content.delete_range(start, end),
+        };
+        self.register = Register {
+            text,
+            linewise: false,
+        };
+        content.cursor_index_y = start.0;
+        content.cursor_index_x = start.1;
+        if operator == Operator::Change {
+            self.mode = VimMode::Insert;
+        } else {
+            clamp_normal_cursor(content);
+        }
+    }
+
+    fn put(&mut self, after: bool, count: usize, content: &mut Content) {
+        if self.register.text.is_empty() {
+            return;
+        }
+        content.push_undo();
+        self.change_made = true;
+        if self.register.linewise {
+            let text: Vec<String> = (0..count)
+                .flat_map(|_| self.register.text.iter().cloned())
+                .collect();
+            let index = if after {
+                content.cursor_index_y + 1
+            } else {
+                content.cursor_index_y
+            };
+            content.insert_lines(index, &text);
+            content.cursor_index_x = first_non_blank(current_line(content));
+            return;
+        }
+        let text = match self.register.text.as_slice() {
+            [single] => vec![single.repeat(count)],
+            text => text.to_vec(),
+        };
+        if after && !current_line(content).is_empty() {
+            content.cursor_index_x =
+                next_char_boundary(current_line(content), content.cursor_index_x);
+        }
+        content.insert_text(&text);
+        content.cursor_index_x = content.cursor_index_x.saturating_sub(1);
+        clamp_normal_cursor(content);
+    }
+
+    pub fn highlights(&self, content: &Content) -> Vec<Highlight> {
+        if !self.enabled || !matches!(self.mode, VimMode::Visual | VimMode::VisualLine) {
+            return Vec::new();
+        }
+        let cursor = (content.cursor_index_y, content.cursor_index_x);
+        let (start, end) = if self.visual_anchor <= cursor {
+            (self.visual_anchor, cursor)
+        } else {
+            (cursor, self.visual_anchor)
+        };
+        let style = Style::default().bg(Color::Blue).fg(Color::White);
+        (start.0..=end.0)
+            .filter_map(|line| {
+                let text = content.content_input.get(line)?;
+                let (from, to) = if self.mode == VimMode::VisualLine {
+                    (0, text.len())
+                } else {
+                    let from = if line == start.0 { start.1 } else { 0 };
+                    let to = if line == end.0 {
+                        next_char_boundary(text, end.1)
+                    } else {
+                        text.len()
+                    };
+                    (from, to)
+                };
+                Some(Highlight {
+                    line,
+                    start: from,
+                    end: to,
+                    style,
+                })
+            })
+            .collect()
+    }
+}
+
+fn current_line(content: &Content) -> &str {
+    content
+        .content_input
+        .get(content.cursor_index_y)
+        .map(String::as_str)
+        .unwrap_or_default()
+}
+
+fn clamp_normal_cursor(content: &mut Content) {
+    content.cursor_index_y = content
+        .cursor_index_y
+        .min(content.content_input.len().saturating_sub(1));
+    let line = current_line(content);
+    let last_char = line
+        .char_indices()
+        .last()
+        .map(|(index, _)| index)
+        .unwrap_or(0);
+    content.cursor_index_x = content.cursor_index_x.min(last_char);
+}
+
+fn next_char_boundary(line: &str, column: usize) -> usize {
+    line.get(column..)
+        .and_then(|rest| rest.chars().next())
+        .map(|c| column + c.len_utf8())
+        .unwrap_or(line.len())
+}
+
+fn previous_char_boundary(line: &str, column: usize) -> usize {
+    line.get(..column)
+        .and_then(|before| before.char_indices().last())
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
+
+fn first_non_blank(line: &str) -> usize {
+    line.find(|c: char| !c.is_whitespace()).unwrap_or(0)
+}
+
+fn char_class(c: char) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+fn positions(lines: &[String]) -> Vec<(usize, usize, u8)> {
+    let mut positions = Vec::new();
+    for (line_index, line) in lines.iter().enumerate() {
+        for (column, c) in line.char_indices() {
+            positions.push((line_index, column, char_class(c)));
+        }
+        positions.push((line_index, line.len(), 0));
+    }
+    positions
+}
+
+fn word_forward(lines: &[String], cursor: (usize, usize)) -> (usize, usize) {
+    let positions = positions(lines);
+    let Some(mut index) = positions
+        .iter()
+        .position(|&(line, column, _)| (line, column) >= cursor)
+    else {
+        return cursor;
+    };
+    let class = positions[index].2;
+    if class != 0 {
+        while index < positions.len() && positions[index].2 == class {
+            index += 1;
+        }
+    }
+    while index < positions.len() && positions[index].2 == 0 {
+        index += 1;
+    }
+    positions
+        .get(index)
+        .or(positions.last())
+        .map(|&(line, column, _)| (line, column))
+        .unwrap_or(cursor)
+}
+
+fn word_end(lines: &[String], cursor: (usize, usize)) -> (usize, usize) {
+    let positions = positions(lines);
+    let Some(mut index) = positions
+        .iter()
+        .position(|&(line, column, _)| (line, column) >= cursor)
+    else {
+        return cursor;
+    };
+    index += 1;
+    while index < positions.len() && positions[index].2 == 0 {
+        index += 1;
+    }
+    let Some(&(_, _, class)) = positions.get(index) else {
+        return cursor;
+    };
+    while index + 1 < positions.len() && positions[index + 1].2 == class {
+        index += 1;
+    }
+    (positions[index].0, positions[index].1)
+}
+
+fn word_backward(lines: &[String], cursor: (usize, usize)) -> (usize, usize) {
+    let positions = positions(lines);
+    let Some(mut index) = positions
+        .iter()
+        .position(|&(line, column, _)| (line, column) >= cursor)
+    else {
+        return cursor;
+    };
+    if index == 0 {
+        return cursor;
+    }
+    index -= 1;
+    while index > 0 && positions[index].2 == 0 {
+        index -= 1;
+    }
+    let class = positions[index].2;
+    while index > 0 && positions[index - 1].2 == class && class != 0 {
+        index -= 1;
+    }
+    (positions[index].0, positions[index].1)
+}
+
+type WordMotion = fn(&[String], (usize, usize)) -> (usize, usize);
+
+fn motion_for_key(
+    code: KeyCode,
+    lines: &[String],
+    cursor: (usize, usize),
+    count: usize,
+) -> Option<Motion> {
+    let (line, column) = cursor;
+    let last_line = lines.len().saturating_sub(1);
+    let text = lines.get(line).map(String::as_str).unwrap_or_default();
+    let repeat = |step: WordMotion| (0..count).fold(cursor, |position, _| step(lines, position));
+    let motion = match code {
+        KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => Motion {
+            target: (
+                line,
+                (0..count).fold(column, |column, _| previous_char_boundary(text, column)),
+            ),
+            kind: MotionKind::Exclusive,
+        },
+        KeyCode::Char('l') | KeyCode::Right | KeyCode::Char(' ') => Motion {
+            target: (
+                line,
+                (0..count).fold(column, |column, _| next_char_boundary(text, column)),
+            ),
+            kind: MotionKind::Exclusive,
+        },
+        KeyCode::Char('j') | KeyCode::Down => Motion {
+            target: ((line + count).min(last_line), column),
+            kind: MotionKind::Linewise,
+        },
+        KeyCode::Char('k') | KeyCode::Up => Motion {
+            target: (line.saturating_sub(count), column),
+            kind: MotionKind::Linewise,
+        },
+        KeyCode::Char('w') => Motion {
+            target: repeat(word_forward),
+            kind: MotionKind::Exclusive,
+        },
+        KeyCode::Char('b') => Motion {
+            target: repeat(word_backward),
+            kind: MotionKind::Exclusive,
+        },
+        KeyCode::Char('e') => Motion {
+            target: repeat(word_end),
+            kind: MotionKind::Inclusive,
+        },
+        KeyCode::Char('0') | KeyCode::Home => Motion {
+            target: (line, 0),
+            kind: MotionKind::Exclusive,
+        },
+        KeyCode::Char('^') => Motion {
+            target: (line, first_non_blank(text)),
+            kind: MotionKind::Exclusive,
+        },
+        KeyCode::Char('$') | KeyCode::End => {
+            let target_line = (line + count - 1).min(last_line);
+            let target_text = lines
+                .get(target_line)
+                .map(String::as_str)
+                .unwrap_or_default();
+            Motion {
+                target: (
+                    target_line,
+                    previous_char_boundary(target_text, target_text.len()),
+                ),
+                kind: MotionKind::Inclusive,
+            }
+        }
+        KeyCode::Char('G') => {
+            let target_line = if count > 1 {
+                (count - 1).min(last_line)
+            } else {
+                last_line
+            };
+            let target_text = lines
+                .get(target_line)
+                .map(String::as_str)
+                .unwrap_or_default();
+            Motion {
+                target: (target_line, first_non_blank(target_text)),
+                kind: MotionKind::Linewise,
+            }
+        }
+        _ => return None,
+    };
+    Some(motion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn content_with(lines: &[&str]) -> Content {
+        let mut content = Content::new();
+        content.content_input = lines.iter().map(|line| line.to_string()).collect();
+        content.set_insert_mode(true);
+        content
+    }
+
+    fn press(vim: &mut Vim, content: &mut Content, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\x1b' => KeyCode::Esc,
+                '\n' => KeyCode::Enter,
+                c => KeyCode::Char(c),
+            };
+            vim.handle_key(KeyEvent::new(code, KeyModifiers::NONE), content);
+        }
+    }
+
+    #[test]
+    fn test_word_motions() {
+        let lines = vec!["foo bar.baz".to_string(), "  qux".to_string()];
+        assert_eq!(word_forward(&lines, (0, 0)), (0, 4));
+        assert_eq!(word_forward(&lines, (0, 4)), (0, 7));
+        assert_eq!(word_forward(&lines, (0, 8)), (1, 2));
+        assert_eq!(word_end(&lines, (0, 0)), (0, 2));
+        assert_eq!(word_end(&lines, (0, 2)), (0, 6));
+        assert_eq!(word_backward(&lines, (1, 2)), (0, 8));
+        assert_eq!(word_backward(&lines, (0, 4)), (0, 0));
+    }
+
+    #[test]
+    fn test_delete_word_with_count() {
+        let mut vim = Vim::new(true);
+        let mut content = content_with(&["one two three four"]);
+        press(&mut vim, &mut content, "2dw");
+        assert_eq!(content.content_input, vec!["three four"]);
+    }
+
+    #[test]
+    fn test_delete_lines_and_put() {
+        let mut vim = Vim::new(true);
+        let mut content = content_with(&["a", "b", "c"]);
+        press(&mut vim, &mut content, "ddp");
+        assert_eq!(content.content_input, vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_linewise_operators_on_empty_buffer() {
+        let mut vim = Vim::new(true);
+        let mut content = content_with(&[]);
+        press(&mut vim, &mut content, "yydd");
+        assert!(content.content_input.is_empty());
+        press(&mut vim, &mut content, "ccnew\x1b");
+        assert_eq!(content.content_input, vec!["new"]);
+    }
+
+    #[test]
+    fn test_change_word_enters_insert_mode() {
+        let mut vim = Vim::new(true);
+        let mut content = content_with(&["hello world"]);
+        press(&mut vim, &mut content, "cwbye\x1b");
+        assert_eq!(content.content_input, vec!["bye world"]);
+        assert_eq!(vim.mode, VimMode::Normal);
+    }
+
+    #[test]
+    fn test_dot_repeats_last_change() {
+        let mut vim = Vim::new(true);
+        let mut content = content_with(&["a b c d"]);
+        press(&mut vim, &mut content, "dw..");
+        assert_eq!(content.content_input, vec!["d"]);
+    }
+
+    #[test]
+    fn test_visual_yank_and_put() {
+        let mut vim = Vim::new(true);
+        let mut content = content_with(&["abc"]);
+        press(&mut vim, &mut content, "vly$p");
+        assert_eq!(content.content_input, vec!["abcab"]);
+    }
+
+    #[test]
+    fn test_goto_first_and_last_line() {
+        let mut vim = Vim::new(true);
+        let mut content = content_with(&["a", "b", "  c"]);
+        press(&mut vim, &mut content, "G");
+        assert_eq!((content.cursor_index_y, content.cursor_index_x), (2, 2));
+        press(&mut vim, &mut content, "gg");
+        assert_eq!(content.cursor_index_y, 0);
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut vim = Vim::new(true);
+        let mut content = content_with(&["a"]);
+        press(&mut vim, &mut content, ":w");
+        let action = vim.handle_key(
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            &mut content,
+        );
+        assert_eq!(action, VimAction::Save);
+
+        press(&mut vim, &mut content, ":wq");
+        let action = vim.handle_key(
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            &mut content,
+        );
+        assert_eq!(action, VimAction::SaveAndQuit);
+
+        press(&mut vim, &mut content, ":q!");
+        let action = vim.handle_key(
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            &mut content,
+        );
+        assert_eq!(action, VimAction::ForceQuit);
+
+        press(&mut vim, &mut content, ":nope\n");
+        assert_eq!(vim.status(), "Not an editor command: nope");
+    }
+}